@@ -47,7 +47,7 @@ use vec_map::VecMap;
 use crypto::{self, Hash, PublicKey, SecretKey};
 use helpers::{Height, ValidatorId};
 use messages::{Precommit, RawMessage, CONSENSUS as CORE_SERVICE};
-use node::ApiSender;
+use node::{ApiSender, TxPool};
 use node::PeerInfo;
 use storage::{Database, Error, Fork, Patch, Snapshot};
 
@@ -390,7 +390,26 @@ impl Blockchain {
 
     /// Returns `Mount` object that aggregates public api handlers.
     pub fn mount_public_api(&self) -> Mount {
-        let context = self.api_context();
+        self.mount_public_api_with_context(self.api_context())
+    }
+
+    /// Returns `Mount` object that aggregates public api handlers, which can
+    /// look into the given pool of unconfirmed transactions.
+    pub fn mount_public_api_with_pool(&self, pool: TxPool) -> Mount {
+        self.mount_public_api_with_context(self.api_context().with_tx_pool(pool))
+    }
+
+    /// Returns `Mount` object that aggregates public api handlers, which can
+    /// look into the given pool of unconfirmed transactions and the state
+    /// shared by the node.
+    pub fn mount_public_api_with_state(&self, pool: TxPool, state: SharedNodeState) -> Mount {
+        let context = self.api_context()
+            .with_tx_pool(pool)
+            .with_node_state(state);
+        self.mount_public_api_with_context(context)
+    }
+
+    fn mount_public_api_with_context(&self, context: ApiContext) -> Mount {
         let mut mount = Mount::new();
         for service in self.service_map.values() {
             if let Some(handler) = service.public_api_handler(&context) {
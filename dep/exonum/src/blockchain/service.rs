@@ -29,7 +29,7 @@ use encoding::serialize::json::ExonumJson;
 use encoding::Error as MessageError;
 use helpers::{Height, Milliseconds, ValidatorId};
 use messages::{Message, RawTransaction};
-use node::{ApiSender, Node, State, TransactionSend};
+use node::{ApiSender, Node, State, TransactionSend, TxPool};
 use storage::{Fork, Snapshot};
 
 /// Transaction processing functionality for `Message`s allowing to apply authenticated, atomic,
@@ -466,6 +466,8 @@ pub struct ApiContext {
     node_channel: ApiSender,
     public_key: PublicKey,
     secret_key: SecretKey,
+    tx_pool: Option<TxPool>,
+    node_state: Option<SharedNodeState>,
 }
 
 /// Provides the current node state to api handlers.
@@ -478,6 +480,8 @@ impl ApiContext {
             node_channel: node.channel(),
             public_key: *node.state().service_public_key(),
             secret_key: node.state().service_secret_key().clone(),
+            tx_pool: Some(Arc::clone(node.state().transactions())),
+            node_state: Some(handler.api_state().clone()),
         }
     }
 
@@ -493,9 +497,23 @@ impl ApiContext {
             node_channel,
             public_key: *public_key,
             secret_key: secret_key.clone(),
+            tx_pool: None,
+            node_state: None,
         }
     }
 
+    /// Attaches the pool of unconfirmed transactions to the context.
+    pub fn with_tx_pool(mut self, tx_pool: TxPool) -> ApiContext {
+        self.tx_pool = Some(tx_pool);
+        self
+    }
+
+    /// Attaches the state shared by the node with its api to the context.
+    pub fn with_node_state(mut self, node_state: SharedNodeState) -> ApiContext {
+        self.node_state = Some(node_state);
+        self
+    }
+
     /// Returns reference to the node's blockchain.
     pub fn blockchain(&self) -> &Blockchain {
         &self.blockchain
@@ -515,6 +533,19 @@ impl ApiContext {
     pub fn secret_key(&self) -> &SecretKey {
         &self.secret_key
     }
+
+    /// Returns the pool of unconfirmed transactions, if the context was
+    /// constructed with one.
+    pub fn tx_pool(&self) -> Option<&TxPool> {
+        self.tx_pool.as_ref()
+    }
+
+    /// Returns the state shared by the node with its api, such as the
+    /// connected peers and the height reported by them, if the context was
+    /// constructed with one.
+    pub fn node_state(&self) -> Option<&SharedNodeState> {
+        self.node_state.as_ref()
+    }
 }
 
 impl ::std::fmt::Debug for ApiContext {
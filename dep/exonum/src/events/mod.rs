@@ -22,6 +22,7 @@ pub mod tests;
 use std::cmp::Ordering;
 use std::time::SystemTime;
 
+use futures::future::{self, Either};
 use futures::sink::Wait;
 use futures::sync::mpsc::{self, Sender};
 use futures::{Async, Future, Poll, Stream};
@@ -68,20 +69,35 @@ pub struct HandlerPart<H: EventHandler> {
     pub internal_rx: mpsc::Receiver<InternalEvent>,
     pub network_rx: mpsc::Receiver<NetworkEvent>,
     pub api_rx: mpsc::Receiver<ExternalMessage>,
+    pub shutdown_rx: mpsc::Receiver<()>,
 }
 
 impl<H: EventHandler + 'static> HandlerPart<H> {
-    pub fn run(self) -> Box<Future<Item = (), Error = ()>> {
+    /// Handles events until a shutdown request is received, which resolves
+    /// the future with `true`, or until an event stream closes. An event being
+    /// handled is always handled completely.
+    pub fn run(self) -> Box<Future<Item = bool, Error = ()>> {
         let mut handler = self.handler;
 
-        let fut = EventsAggregator::new(self.internal_rx, self.network_rx, self.api_rx).for_each(
-            move |event| {
+        let events = EventsAggregator::new(self.internal_rx, self.network_rx, self.api_rx)
+            .for_each(move |event| {
                 handler.handle_event(event);
                 Ok(())
-            },
-        );
-
-        to_box(fut)
+            })
+            .map(|_| false);
+        let shutdown = self.shutdown_rx
+            .into_future()
+            .then(|received| match received {
+                Ok((Some(()), _)) => Either::A(future::ok::<bool, ()>(true)),
+                // Nothing can ask for a shutdown anymore.
+                _ => Either::B(future::empty::<bool, ()>()),
+            });
+
+        let fut = events
+            .select(shutdown)
+            .map(|(stopped, _)| stopped)
+            .map_err(drop);
+        Box::new(fut)
     }
 }
 
@@ -37,8 +37,7 @@ pub mod metrics;
 
 /// Performs the logger initialization.
 pub fn init_logger() -> Result<(), SetLoggerError> {
-    let mut builder = Builder::new();
-    builder.format(format_log_record);
+    let mut builder = logger_builder();
 
     if env::var("RUST_LOG").is_ok() {
         builder.parse(&env::var("RUST_LOG").unwrap());
@@ -47,6 +46,14 @@ pub fn init_logger() -> Result<(), SetLoggerError> {
     builder.try_init()
 }
 
+/// Returns a logger builder with the default record format and no filters, so
+/// that applications can set their own levels before initialization.
+pub fn logger_builder() -> Builder {
+    let mut builder = Builder::new();
+    builder.format(format_log_record);
+    builder
+}
+
 /// Generates testnet configuration.
 pub fn generate_testnet_config(count: u8, start_port: u16) -> Vec<NodeConfig> {
     let (validators, services): (Vec<_>, Vec<_>) = (0..count as usize)
@@ -97,7 +97,10 @@ pub trait SystemStateProvider: ::std::fmt::Debug {
 
 /// Transactions sender.
 #[derive(Clone)]
-pub struct ApiSender(pub mpsc::Sender<ExternalMessage>);
+pub struct ApiSender(
+    pub mpsc::Sender<ExternalMessage>,
+    Option<mpsc::Sender<()>>,
+);
 
 /// Handler that that performs consensus algorithm.
 pub struct NodeHandler {
@@ -642,7 +645,16 @@ pub trait TransactionSend: Send + Sync {
 impl ApiSender {
     /// Creates new `ApiSender` with given channel.
     pub fn new(inner: mpsc::Sender<ExternalMessage>) -> ApiSender {
-        ApiSender(inner)
+        ApiSender(inner, None)
+    }
+
+    /// Creates new `ApiSender` with given channel, which can also stop the
+    /// node through the `shutdown` channel.
+    pub fn with_shutdown(
+        inner: mpsc::Sender<ExternalMessage>,
+        shutdown: mpsc::Sender<()>,
+    ) -> ApiSender {
+        ApiSender(inner, Some(shutdown))
     }
 
     /// Add peer to peer list
@@ -655,6 +667,21 @@ impl ApiSender {
             .map(drop)
             .map_err(into_other)
     }
+
+    /// Ask the node to stop; `Node::run` returns once it has. An event being
+    /// handled is handled completely, so a block being committed is always
+    /// written completely.
+    pub fn shutdown(&self) -> io::Result<()> {
+        let shutdown = self.1
+            .as_ref()
+            .ok_or_else(|| other_error("This sender can't stop the node"))?;
+        shutdown
+            .clone()
+            .send(())
+            .wait()
+            .map(drop)
+            .map_err(into_other)
+    }
 }
 
 impl TransactionSend for ApiSender {
@@ -712,6 +739,8 @@ pub struct NodeChannel {
     pub network_events: (mpsc::Sender<NetworkEvent>, mpsc::Receiver<NetworkEvent>),
     /// Channel for internal events.
     pub internal_events: (mpsc::Sender<InternalEvent>, mpsc::Receiver<InternalEvent>),
+    /// Channel for shutdown requests.
+    pub shutdown: (mpsc::Sender<()>, mpsc::Receiver<()>),
 }
 
 const PROFILE_ENV_VARIABLE_NAME: &str = "EXONUM_PROFILE_FILENAME";
@@ -735,6 +764,7 @@ impl NodeChannel {
             api_requests: mpsc::channel(buffer_sizes.api_requests_capacity),
             network_events: mpsc::channel(buffer_sizes.network_events_capacity),
             internal_events: mpsc::channel(buffer_sizes.internal_events_capacity),
+            shutdown: mpsc::channel(1),
         }
     }
 
@@ -818,6 +848,9 @@ impl Node {
 
     /// Launches only consensus messages handler.
     /// This may be used if you want to customize api with the `ApiContext`.
+    ///
+    /// Returns after `ApiSender::shutdown` is called. The network
+    /// thread is not joined and stops with the process.
     pub fn run_handler(mut self) -> io::Result<()> {
         self.handler.initialize();
 
@@ -835,8 +868,13 @@ impl Node {
         });
 
         let mut core = Core::new()?;
-        core.run(handler_part.run())
+        let shutdown = core
+            .run(handler_part.run())
             .map_err(|_| other_error("An error in the `Handler` thread occurred"))?;
+        if shutdown {
+            info!("Node has stopped");
+            return Ok(());
+        }
         network_thread.join().unwrap()
     }
 
@@ -845,6 +883,9 @@ impl Node {
     /// Explorer api prefix is `/api/explorer`
     /// Public api prefix is `/api/services/{service_name}`
     /// Private api prefix is `/api/services/{service_name}`
+    ///
+    /// Returns after `ApiSender::shutdown` is called, leaving the api
+    /// threads to stop with the process.
     pub fn run(self) -> io::Result<()> {
         let blockchain = self.handler().blockchain.clone();
         let api_sender = self.channel();
@@ -883,12 +924,9 @@ impl Node {
 
         self.run_handler()?;
 
-        if let Some(private_config_api_thread) = private_config_api_thread {
-            private_config_api_thread.join().unwrap();
-        }
-        if let Some(public_config_api_thread) = public_config_api_thread {
-            public_config_api_thread.join().unwrap();
-        }
+        // The api servers never return, so the threads are detached.
+        drop(private_config_api_thread);
+        drop(public_config_api_thread);
 
         Ok(())
     }
@@ -912,6 +950,7 @@ impl Node {
             internal_rx,
             network_rx,
             api_rx: self.channel.api_requests.1,
+            shutdown_rx: self.channel.shutdown.1,
         };
 
         let timeouts_part = InternalPart {
@@ -938,7 +977,10 @@ impl Node {
 
     /// Returns channel.
     pub fn channel(&self) -> ApiSender {
-        ApiSender::new(self.channel.api_requests.0.clone())
+        ApiSender::with_shutdown(
+            self.channel.api_requests.0.clone(),
+            self.channel.shutdown.0.clone(),
+        )
     }
 }
 
@@ -951,7 +993,10 @@ pub fn create_public_api_handler(
     config: &NodeApiConfig,
 ) -> Chain {
     let mut mount = Mount::new();
-    mount.mount("api/services", blockchain.mount_public_api());
+    mount.mount(
+        "api/services",
+        blockchain.mount_public_api_with_state(Arc::clone(&pool), shared_api_state.clone()),
+    );
 
     if config.enable_blockchain_explorer {
         let mut router = Router::new();
@@ -116,7 +116,7 @@ pub use self::db::{
 pub use self::error::Error;
 
 pub use self::memorydb::MemoryDB;
-pub use self::rocksdb::{RocksDB, RocksDBOptions};
+pub use self::rocksdb::{sync_parent, RocksDB, RocksDBOptions};
 
 pub use self::keys::StorageKey;
 pub use self::values::StorageValue;
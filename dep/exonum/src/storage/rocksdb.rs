@@ -16,9 +16,11 @@
 
 use std::error;
 use std::fmt;
+use std::fs;
+use std::io;
 use std::iter::Peekable;
 use std::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use exonum_profiler::ProfilerSpan;
@@ -26,7 +28,7 @@ use rocksdb::utils::get_cf_names;
 use rocksdb::Error as _Error;
 use rocksdb::Snapshot as _Snapshot;
 use rocksdb::DB as _RocksDB;
-use rocksdb::{DBIterator, WriteBatch};
+use rocksdb::{DBIterator, IteratorMode, WriteBatch};
 
 use super::db::Change;
 use super::{Database, Error, Iter, Iterator, Patch, Result, Snapshot};
@@ -40,9 +42,30 @@ impl From<_Error> for Error {
     }
 }
 
+/// Size of the write batches used when copying a checkpoint.
+const CHECKPOINT_BATCH_SIZE: usize = 4 * 1024 * 1024;
+
+fn io_error(path: &Path, e: &io::Error) -> Error {
+    Error::new(format!("{}: {}", path.display(), e))
+}
+
+/// Make a rename of `path` durable by syncing the directory it is in.
+pub fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+        Some(parent) => parent,
+        None => return Ok(()),
+    };
+    fs::File::open(parent)?.sync_all()
+}
+
 /// Database implementation on the top of `RocksDB` backend.
+///
+/// Clones share the same underlying database.
+#[derive(Clone)]
 pub struct RocksDB {
     db: Arc<_RocksDB>,
+    path: PathBuf,
 }
 
 /// A snapshot of a `RocksDB`.
@@ -61,15 +84,128 @@ struct RocksDBIterator {
 impl RocksDB {
     /// Open a database stored in the specified path with the specified options.
     pub fn open<P: AsRef<Path>>(path: P, options: &RocksDBOptions) -> Result<RocksDB> {
+        let path = path.as_ref().to_path_buf();
         let db = {
             if let Ok(names) = get_cf_names(&path) {
                 let cf_names = names.iter().map(|name| name.as_str()).collect::<Vec<_>>();
-                _RocksDB::open_cf(options, path, cf_names.as_ref())?
+                _RocksDB::open_cf(options, &path, cf_names.as_ref())?
             } else {
-                _RocksDB::open(options, path)?
+                _RocksDB::open(options, &path)?
             }
         };
-        Ok(RocksDB { db: Arc::new(db) })
+        Ok(RocksDB {
+            db: Arc::new(db),
+            path,
+        })
+    }
+
+    /// Open an existing database stored in the specified path for reading
+    /// only. Nothing is written to the database, not even its logs, and it
+    /// can be opened while another process writes to it; those writes are
+    /// not seen. Merging patches into such a database fails.
+    pub fn open_read_only<P: AsRef<Path>>(path: P, options: &RocksDBOptions) -> Result<RocksDB> {
+        let path = path.as_ref().to_path_buf();
+        let db = {
+            if let Ok(names) = get_cf_names(&path) {
+                let cf_names = names.iter().map(|name| name.as_str()).collect::<Vec<_>>();
+                _RocksDB::open_cf_for_read_only(options, &path, cf_names.as_ref(), false)?
+            } else {
+                _RocksDB::open_for_read_only(options, &path, false)?
+            }
+        };
+        Ok(RocksDB {
+            db: Arc::new(db),
+            path,
+        })
+    }
+
+    /// Write a consistent copy of the database to a new database at `path`.
+    ///
+    /// The copy is made from a snapshot, so it can be taken while the
+    /// database is being written to and reflects the state at the moment
+    /// the method was called. `path` must not exist.
+    ///
+    /// The copy is written to `<path>.tmp` and only renamed to `path` once
+    /// complete, so a failed checkpoint leaves nothing at `path` and can be
+    /// retried.
+    pub fn checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let _p = ProfilerSpan::new("RocksDB::checkpoint");
+        let path = path.as_ref();
+        if path.exists() {
+            return Err(Error::new(format!("{} already exists", path.display())));
+        }
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        // Left over by a checkpoint interrupted by a crash.
+        if tmp.exists() {
+            fs::remove_dir_all(&tmp).map_err(|e| io_error(&tmp, &e))?;
+        }
+
+        let written = self.write_checkpoint(&tmp).and_then(|_| {
+            fs::rename(&tmp, path).map_err(|e| io_error(path, &e))?;
+            sync_parent(path).map_err(|e| io_error(path, &e))
+        });
+        if written.is_err() && tmp.exists() {
+            let _ = fs::remove_dir_all(&tmp);
+        }
+        written
+    }
+
+    /// Copy a snapshot of the database to a new database at `path`.
+    ///
+    /// This is a logical copy rather than a native RocksDB checkpoint of hard
+    /// linked files: `exonum_rocksdb` binds neither the checkpoint API nor the
+    /// raw database handle it would need.
+    fn write_checkpoint(&self, path: &Path) -> Result<()> {
+        // Take the snapshot first, so that every column family it covers is
+        // listed.
+        let snapshot = self.db.snapshot();
+        let names = get_cf_names(&self.path)
+            .map_err(|_| Error::new("Unable to list column families"))?;
+
+        let mut options = RocksDBOptions::default();
+        options.create_if_missing(true);
+        let target = _RocksDB::open(&options, path)?;
+        let mut w_opts = RocksDBWriteOptions::default();
+        w_opts.set_sync(true);
+
+        for name in names {
+            // The default column family is never written to, and exists in
+            // the target already.
+            if name == "default" {
+                continue;
+            }
+            let cf = match self.db.cf_handle(&name) {
+                Some(cf) => cf,
+                None => continue,
+            };
+            let target_cf = target.create_cf(&name, &RocksDBOptions::default())?;
+            let mut batch = WriteBatch::default();
+            let mut batch_size = 0;
+            for (key, value) in snapshot.iterator_cf(cf, IteratorMode::Start)? {
+                batch.put_cf(target_cf, &key, &value)?;
+                batch_size += key.len() + value.len();
+                if batch_size >= CHECKPOINT_BATCH_SIZE {
+                    let full = mem::replace(&mut batch, WriteBatch::default());
+                    target.write_opt(full, &w_opts)?;
+                    batch_size = 0;
+                }
+            }
+            target.write_opt(batch, &w_opts)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the write-ahead log to disk, making every merged patch durable.
+    pub fn sync(&self) -> Result<()> {
+        let _p = ProfilerSpan::new("RocksDB::sync");
+        let mut w_opts = RocksDBWriteOptions::default();
+        w_opts.set_sync(true);
+        self.db
+            .write_opt(WriteBatch::default(), &w_opts)
+            .map_err(Into::into)
     }
 
     fn do_merge(&self, patch: Patch, w_opts: &RocksDBWriteOptions) -> Result<()> {
@@ -129,7 +265,7 @@ impl Snapshot for RocksDBSnapshot {
     }
 
     fn iter<'a>(&'a self, name: &str, from: &[u8]) -> Iter<'a> {
-        use rocksdb::{Direction, IteratorMode};
+        use rocksdb::Direction;
         let _p = ProfilerSpan::new("RocksDBSnapshot::iter");
         let iter = match self._db.cf_handle(name) {
             Some(cf) => self
@@ -221,4 +221,84 @@ mod rocksdb_tests {
         let path = dir.path();
         super::changelog(rocksdb_database(path));
     }
+
+    #[test]
+    fn test_rocksdb_checkpoint() {
+        use super::super::Database;
+
+        let dir = TempDir::new("exonum_rocksdb3").unwrap();
+        let db = rocksdb_database(&dir.path().join("db"));
+        let mut fork = db.fork();
+        fork.put(super::IDX_NAME, vec![10], vec![10]);
+        fork.put("other_idx", vec![20], vec![20]);
+        db.merge(fork.into_patch()).unwrap();
+
+        let checkpoint_path = dir.path().join("checkpoint");
+        db.checkpoint(&checkpoint_path).unwrap();
+        assert!(db.checkpoint(&checkpoint_path).is_err());
+
+        // Writes after the checkpoint are not in it.
+        let mut fork = db.fork();
+        fork.put(super::IDX_NAME, vec![30], vec![30]);
+        db.merge(fork.into_patch()).unwrap();
+
+        let checkpoint = rocksdb_database(&checkpoint_path);
+        let snapshot = checkpoint.snapshot();
+        assert_eq!(snapshot.get(super::IDX_NAME, &[10]), Some(vec![10]));
+        assert_eq!(snapshot.get("other_idx", &[20]), Some(vec![20]));
+        assert_eq!(snapshot.get(super::IDX_NAME, &[30]), None);
+    }
+
+    #[test]
+    fn test_rocksdb_checkpoint_retry() {
+        use std::fs;
+
+        let dir = TempDir::new("exonum_rocksdb4").unwrap();
+        let db = rocksdb_database(&dir.path().join("db"));
+
+        // Fails before anything is copied: the parent directory is missing.
+        let checkpoint_path = dir.path().join("missing").join("checkpoint");
+        assert!(db.checkpoint(&checkpoint_path).is_err());
+        assert!(!checkpoint_path.exists());
+        assert!(!dir.path().join("missing").join("checkpoint.tmp").exists());
+
+        // A copy left over by an interrupted checkpoint is replaced.
+        let checkpoint_path = dir.path().join("checkpoint");
+        let tmp_path = dir.path().join("checkpoint.tmp");
+        fs::create_dir(&tmp_path).unwrap();
+        fs::write(tmp_path.join("CURRENT"), b"garbage").unwrap();
+        db.checkpoint(&checkpoint_path).unwrap();
+        assert!(checkpoint_path.join("CURRENT").exists());
+        assert!(!tmp_path.exists());
+    }
+
+    #[test]
+    fn test_rocksdb_read_only() {
+        use super::super::Database;
+
+        let dir = TempDir::new("exonum_rocksdb5").unwrap();
+        let path = dir.path().join("db");
+        assert!(RocksDB::open_read_only(&path, &RocksDBOptions::default()).is_err());
+        assert!(!path.exists());
+
+        let db = rocksdb_database(&path);
+        let mut fork = db.fork();
+        fork.put(super::IDX_NAME, vec![10], vec![10]);
+        fork.put("other_idx", vec![20], vec![20]);
+        db.merge(fork.into_patch()).unwrap();
+
+        // Opened next to the writer, which keeps working.
+        let read_only = RocksDB::open_read_only(&path, &RocksDBOptions::default()).unwrap();
+        let snapshot = read_only.snapshot();
+        assert_eq!(snapshot.get(super::IDX_NAME, &[10]), Some(vec![10]));
+        assert_eq!(snapshot.get("other_idx", &[20]), Some(vec![20]));
+
+        let mut fork = read_only.fork();
+        fork.put(super::IDX_NAME, vec![30], vec![30]);
+        assert!(read_only.merge(fork.into_patch()).is_err());
+
+        let mut fork = db.fork();
+        fork.put(super::IDX_NAME, vec![30], vec![30]);
+        db.merge(fork.into_patch()).unwrap();
+    }
 }
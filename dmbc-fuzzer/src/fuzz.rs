@@ -44,7 +44,7 @@ fn main() {
 
         let mut testkit = TestKitBuilder::validator()
             .with_validators(1)
-            .with_service(Service())
+            .with_service(Service::new())
             .create();
 
         testkit.create_block();
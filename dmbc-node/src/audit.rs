@@ -0,0 +1,31 @@
+use std::process;
+
+use exonum::storage::{Database, RocksDB, RocksDBOptions};
+use serde_json;
+
+use dmbc::config;
+use dmbc::currency::audit;
+
+/// Walk the database and print an asset audit report as JSON.
+///
+/// The database is opened read-only, so it can be audited while the node
+/// runs; blocks committed after it was opened are not audited.
+/// Exits with status 2 if inconsistencies were found.
+pub fn run(db_path: Option<String>) {
+    let path = db_path.unwrap_or_else(|| config::config().db().path());
+
+    let db = match RocksDB::open_read_only(&path, &RocksDBOptions::default()) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Unable to open database at {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+
+    let report = audit::audit(db.snapshot());
+    println!("{}", serde_json::to_string_pretty(&report).unwrap());
+
+    if !report.is_clean() {
+        process::exit(2);
+    }
+}
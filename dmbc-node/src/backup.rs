@@ -0,0 +1,146 @@
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use curl::easy::{Easy, List};
+use exonum::storage::sync_parent;
+use serde_json;
+
+use dmbc::config;
+use dmbc::currency::backup;
+use dmbc::currency::SERVICE_NAME;
+
+/// Ask the running node to write a checkpoint named `name` to its backup
+/// directory, `db.backup_dir` in its configuration, and print its summary.
+pub fn run(name: &str, api: Option<String>) {
+    let api = api.unwrap_or_else(default_api);
+    let url = format!("{}/api/services/{}/v1/backup", api, SERVICE_NAME);
+    let body = json!({ "name": name }).to_string();
+
+    match post(&url, &body) {
+        Ok((200, response)) => println!("{}", response),
+        Ok((code, response)) => {
+            eprintln!("Backup failed with status {}: {}", code, response);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Unable to reach the private API at {}: {}", api, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Check the checkpoint at `checkpoint` and copy it to the database path.
+/// The node must be stopped. An existing database is moved aside to
+/// `<path>.bak` when `force` is set and is kept otherwise.
+///
+/// The checkpoint is copied and synced to `<path>.restore` first, and only
+/// then swapped in by renames, so a failed copy leaves the database as it
+/// was.
+pub fn restore(checkpoint: &str, db_path: Option<String>, force: bool) {
+    let summary = match backup::verify(checkpoint) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("{} is not a valid checkpoint: {}", checkpoint, e);
+            process::exit(1);
+        }
+    };
+
+    let path = PathBuf::from(db_path.unwrap_or_else(|| config::config().db().path()));
+    let aside = PathBuf::from(format!("{}.bak", path.display()));
+    if path.exists() {
+        if !force {
+            eprintln!(
+                "{} already exists, use --force to move it aside and restore",
+                path.display()
+            );
+            process::exit(1);
+        }
+        if aside.exists() {
+            eprintln!("{} already exists, remove it first", aside.display());
+            process::exit(1);
+        }
+    }
+
+    let staging = PathBuf::from(format!("{}.restore", path.display()));
+    if staging.exists() {
+        // Left over by an interrupted restore.
+        if let Err(e) = fs::remove_dir_all(&staging) {
+            eprintln!("Unable to remove {}: {}", staging.display(), e);
+            process::exit(1);
+        }
+    }
+    if let Err(e) = copy_dir(Path::new(checkpoint), &staging) {
+        eprintln!("Unable to copy the checkpoint to {}: {}", staging.display(), e);
+        let _ = fs::remove_dir_all(&staging);
+        process::exit(1);
+    }
+
+    if path.exists() {
+        if let Err(e) = fs::rename(&path, &aside) {
+            eprintln!("Unable to move {} aside: {}", path.display(), e);
+            let _ = fs::remove_dir_all(&staging);
+            process::exit(1);
+        }
+        eprintln!("Moved the existing database to {}", aside.display());
+    }
+    if let Err(e) = fs::rename(&staging, &path).and_then(|_| sync_parent(&path)) {
+        eprintln!("Unable to move {} to {}: {}", staging.display(), path.display(), e);
+        if aside.exists() && !path.exists() && fs::rename(&aside, &path).is_ok() {
+            eprintln!("Moved the existing database back to {}", path.display());
+        }
+        process::exit(1);
+    }
+    println!("{}", serde_json::to_string_pretty(&summary).unwrap());
+}
+
+fn default_api() -> String {
+    let mut address = config::config().api().private_address();
+    if address.ip().is_unspecified() {
+        address.set_ip(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)));
+    }
+    format!("http://{}", address)
+}
+
+fn post(url: &str, body: &str) -> Result<(u32, String), ::curl::Error> {
+    let mut response = Vec::new();
+    let mut easy = Easy::new();
+    easy.url(url)?;
+    easy.post(true)?;
+    easy.post_fields_copy(body.as_bytes())?;
+    let mut headers = List::new();
+    headers.append("Content-Type: application/json")?;
+    if let Some(key) = config::config().auth().private_keys().first() {
+        headers.append(&format!("Authorization: Bearer {}", key))?;
+    }
+    easy.http_headers(headers)?;
+    {
+        let mut transfer = easy.transfer();
+        transfer.write_function(|data| {
+            response.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()?;
+    }
+    let code = easy.response_code()?;
+    Ok((code, String::from_utf8_lossy(&response).into_owned()))
+}
+
+/// Copy the directory `from` to `to`, syncing every file and directory.
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+            fs::File::open(&target)?.sync_all()?;
+        }
+    }
+    fs::File::open(to)?.sync_all()
+}
+
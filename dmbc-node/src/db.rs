@@ -0,0 +1,36 @@
+use exonum::storage::{Database, Patch, Result, RocksDB, Snapshot};
+
+use dmbc::config;
+
+/// `RocksDB` that writes every commit with `merge_sync`.
+struct SyncedRocksDB(RocksDB);
+
+impl Database for SyncedRocksDB {
+    fn snapshot(&self) -> Box<Snapshot> {
+        self.0.snapshot()
+    }
+
+    fn merge(&self, patch: Patch) -> Result<()> {
+        self.0.merge_sync(patch)
+    }
+
+    fn merge_sync(&self, patch: Patch) -> Result<()> {
+        self.0.merge_sync(patch)
+    }
+}
+
+/// Open the node database at `path` with the options from the `[db]`
+/// section.
+pub fn open(path: &str) -> Result<RocksDB> {
+    RocksDB::open(path, &config::config().db().options())
+}
+
+/// Wrap the node database for the blockchain, applying the configured
+/// write mode.
+pub fn boxed(db: RocksDB) -> Box<Database> {
+    if config::config().db().sync_commits() {
+        Box::new(SyncedRocksDB(db))
+    } else {
+        Box::new(db)
+    }
+}
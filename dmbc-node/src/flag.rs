@@ -1,12 +1,69 @@
 extern crate clap;
 
 use super::VERSION;
-use clap::{App, Arg};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 
-pub struct Flags {}
+use dmbc::config;
+
+/// Action requested on the command line.
+pub enum Command {
+    /// Start the node.
+    Run {
+        genesis_path: Option<String>,
+        state_path: Option<String>,
+    },
+    /// Generate consensus and service key pairs.
+    GenerateKeys { output: Option<String>, force: bool },
+    /// Encrypt plaintext secret keys in place.
+    EncryptKeys { keys_path: Option<String> },
+    /// Build a genesis configuration from validator public keys.
+    GenerateGenesis {
+        validators: Vec<String>,
+        output: Option<String>,
+    },
+    /// Print a summary of the database contents.
+    InspectDb { db_path: Option<String> },
+    /// Write the service state to JSON lines.
+    ExportState {
+        db_path: Option<String>,
+        output: Option<String>,
+    },
+    /// Print the resolved configuration.
+    PrintConfig,
+    /// Audit asset accounting in the database and exit.
+    AuditAssets { db_path: Option<String> },
+    /// Ask the running node for a checkpoint of its database.
+    Backup { name: String, api: Option<String> },
+    /// Replace the database with a checkpoint.
+    Restore {
+        checkpoint: String,
+        db_path: Option<String>,
+        force: bool,
+    },
+}
+
+pub struct Flags {
+    /// Path to the configuration file.
+    pub config_path: String,
+    /// Path to the file holding the keystore passphrase.
+    pub passphrase_file: Option<String>,
+    pub command: Command,
+}
+
+fn db_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("db")
+        .help("path to the database, defaults to the configured one")
+        .long("db")
+        .takes_value(true)
+}
+
+fn value(matches: &ArgMatches, name: &str) -> Option<String> {
+    matches.value_of(name).map(|v| v.to_string())
+}
 
 pub fn parse() -> Option<Flags> {
     let matches = App::new("DMarket blockchain")
+        .setting(AppSettings::VersionlessSubcommands)
         .arg(
             Arg::with_name("version")
                 .help("show version")
@@ -14,13 +71,202 @@ pub fn parse() -> Option<Flags> {
                 .long("version")
                 .multiple(false),
         )
+        .arg(
+            Arg::with_name("config")
+                .help("path to the configuration file, defaults to $CONFIG_PATH or ./etc/config.toml")
+                .short("c")
+                .long("config")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("passphrase-file")
+                .help("file holding the keystore passphrase, defaults to $DMBC_KEYS_PASSPHRASE_FILE")
+                .long("passphrase-file")
+                .takes_value(true)
+                .global(true),
+        )
+        .subcommand(
+            SubCommand::with_name("run")
+                .about("start the node; the default when no subcommand is given")
+                .arg(
+                    Arg::with_name("genesis")
+                        .help("genesis file in TOML or JSON, defaults to the public network genesis")
+                        .long("genesis")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("state")
+                        .help("state dump written by export-state to seed a new chain with")
+                        .long("state")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("generate-keys")
+                .about("generate consensus and service key pairs")
+                .arg(
+                    Arg::with_name("output")
+                        .help("directory to write the keys to, defaults to the configured one")
+                        .long("output")
+                        .short("o")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("force")
+                        .help("overwrite existing keys")
+                        .long("force"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("encrypt-keys")
+                .about("encrypt plaintext secret keys with a passphrase")
+                .arg(
+                    Arg::with_name("keys")
+                        .help("key directory, defaults to the configured one")
+                        .long("keys")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("generate-genesis")
+                .about("build a genesis configuration from validator keys")
+                .arg(
+                    Arg::with_name("validators")
+                        .help("key directories of the validators, as written by generate-keys")
+                        .required(true)
+                        .multiple(true),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .help("file to write the genesis configuration to, defaults to stdout")
+                        .long("output")
+                        .short("o")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("inspect-db")
+                .about("print a summary of the database contents")
+                .arg(db_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("export-state")
+                .about("write wallets, assets, offers and statuses at the last block as JSON lines")
+                .arg(db_arg())
+                .arg(
+                    Arg::with_name("output")
+                        .help("file to write the dump to, defaults to stdout")
+                        .long("output")
+                        .short("o")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("print-config")
+                .about("print the configuration with defaults and environment overrides applied"),
+        )
+        .subcommand(
+            SubCommand::with_name("audit-assets")
+                .about("check asset amounts against wallet holdings and open offers")
+                .arg(db_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("backup")
+                .about("write a checkpoint of the running node's database")
+                .arg(
+                    Arg::with_name("name")
+                        .help("name of the checkpoint in the node's backup directory; must not exist")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("api")
+                        .help("private API URL of the node, defaults to the configured address")
+                        .long("api")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("restore")
+                .about("replace the database of a stopped node with a checkpoint")
+                .arg(
+                    Arg::with_name("checkpoint")
+                        .help("checkpoint directory written by `backup`")
+                        .required(true),
+                )
+                .arg(db_arg())
+                .arg(
+                    Arg::with_name("force")
+                        .help("move an existing database to <path>.bak instead of failing")
+                        .long("force"),
+                ),
+        )
         .get_matches();
 
-    match matches.occurrences_of("version") {
-        1 => {
-            println!("DMarket Blockchain v{}", VERSION);
-            None
-        }
-        _ => return Some(Flags {}),
+    if matches.occurrences_of("version") == 1 {
+        println!("DMarket Blockchain v{}", VERSION);
+        return None;
     }
+
+    // Global options may be given before or after the subcommand.
+    let global = |name: &str| {
+        matches
+            .subcommand()
+            .1
+            .and_then(|m| value(m, name))
+            .or_else(|| value(&matches, name))
+    };
+    let config_path = global("config").unwrap_or_else(config::default_path);
+    let passphrase_file = global("passphrase-file");
+
+    let command = match matches.subcommand() {
+        ("generate-keys", Some(m)) => Command::GenerateKeys {
+            output: value(m, "output"),
+            force: m.is_present("force"),
+        },
+        ("encrypt-keys", Some(m)) => Command::EncryptKeys {
+            keys_path: value(m, "keys"),
+        },
+        ("generate-genesis", Some(m)) => Command::GenerateGenesis {
+            validators: m
+                .values_of("validators")
+                .map(|v| v.map(|p| p.to_string()).collect())
+                .unwrap_or_default(),
+            output: value(m, "output"),
+        },
+        ("inspect-db", Some(m)) => Command::InspectDb {
+            db_path: value(m, "db"),
+        },
+        ("export-state", Some(m)) => Command::ExportState {
+            db_path: value(m, "db"),
+            output: value(m, "output"),
+        },
+        ("print-config", Some(_)) => Command::PrintConfig,
+        ("audit-assets", Some(m)) => Command::AuditAssets {
+            db_path: value(m, "db"),
+        },
+        ("backup", Some(m)) => Command::Backup {
+            name: value(m, "name").unwrap_or_default(),
+            api: value(m, "api"),
+        },
+        ("restore", Some(m)) => Command::Restore {
+            checkpoint: value(m, "checkpoint").unwrap_or_default(),
+            db_path: value(m, "db"),
+            force: m.is_present("force"),
+        },
+        ("run", Some(m)) => Command::Run {
+            genesis_path: value(m, "genesis"),
+            state_path: value(m, "state"),
+        },
+        _ => Command::Run {
+            genesis_path: None,
+            state_path: None,
+        },
+    };
+
+    Some(Flags {
+        config_path,
+        passphrase_file,
+        command,
+    })
 }
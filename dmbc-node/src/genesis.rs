@@ -0,0 +1,59 @@
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use exonum::blockchain::{GenesisConfig, ValidatorKeys};
+use serde_json;
+
+use dmbc::config;
+use dmbc::currency::genesis::Genesis;
+use keyfile;
+
+/// Build a genesis with a validator for every key directory and the
+/// configured consensus parameters. Everything else is taken from the public
+/// network genesis and is meant to be edited.
+pub fn generate<P: AsRef<Path>>(key_dirs: &[P]) -> io::Result<Genesis> {
+    let mut validators = Vec::new();
+    for dir in key_dirs {
+        validators.push(ValidatorKeys {
+            consensus_key: keyfile::public_key(dir, "consensus")?,
+            service_key: keyfile::public_key(dir, "service")?,
+        });
+    }
+    Ok(Genesis {
+        validators,
+        consensus: config::config().consensus(),
+        ..Genesis::default()
+    })
+}
+
+/// Read and validate a genesis file.
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Genesis> {
+    let genesis = Genesis::load(path)?;
+    genesis.validate().map_err(|errors| {
+        io::Error::new(io::ErrorKind::InvalidData, errors.join("; "))
+    })?;
+    Ok(genesis)
+}
+
+/// Write the genesis to `path`, as JSON if the name ends with `.json` and as
+/// TOML otherwise, or as TOML to stdout.
+pub fn save(genesis: &Genesis, path: Option<&str>) -> io::Result<()> {
+    match path {
+        Some(path) if path.ends_with(".json") => {
+            let content = serde_json::to_string_pretty(genesis).unwrap();
+            writeln!(File::create(path)?, "{}", content)
+        }
+        Some(path) => write!(File::create(path)?, "{}", genesis.to_toml()),
+        None => write!(io::stdout(), "{}", genesis.to_toml()),
+    }
+}
+
+/// Part of the genesis handled by the Exonum core.
+pub fn core_config(genesis: &Genesis) -> GenesisConfig {
+    GenesisConfig::new_with_consensus(
+        genesis.consensus.clone(),
+        genesis.validators.clone().into_iter(),
+    )
+}
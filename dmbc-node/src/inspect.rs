@@ -0,0 +1,64 @@
+use std::process;
+
+use exonum::blockchain;
+use exonum::crypto::Hash;
+use exonum::storage::{Database, RocksDB, RocksDBOptions, StorageValue};
+use serde_json;
+
+use dmbc::config;
+use dmbc::currency::{assets, offers, supply, wallet};
+
+/// Summary of the database contents.
+#[derive(Serialize)]
+struct Summary {
+    height: Option<u64>,
+    block_hash: Option<Hash>,
+    state_hash: Option<Hash>,
+    transactions: u64,
+    wallets: u64,
+    assets: u64,
+    open_offers: u64,
+    coin_supply: Option<u64>,
+}
+
+/// Print a JSON summary of the database.
+///
+/// The database is opened read-only, so it can be inspected while the node
+/// runs; blocks committed after it was opened are not seen.
+pub fn run(db_path: Option<String>) {
+    let path = db_path.unwrap_or_else(|| config::config().db().path());
+
+    let db = match RocksDB::open_read_only(&path, &RocksDBOptions::default()) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Unable to open database at {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+
+    let snapshot = db.snapshot();
+    let view = snapshot.as_ref();
+    let schema = blockchain::Schema::new(view);
+    let last_block = if schema.block_hashes_by_height().len() > 0 {
+        Some(schema.last_block())
+    } else {
+        None
+    };
+
+    let summary = Summary {
+        height: last_block.as_ref().map(|b| b.height().0),
+        block_hash: last_block.as_ref().map(|b| b.hash()),
+        state_hash: last_block.as_ref().map(|b| *b.state_hash()),
+        transactions: schema.transactions().keys().count() as u64,
+        wallets: wallet::Schema(view).index().keys().count() as u64,
+        assets: assets::Schema(view).index().keys().count() as u64,
+        open_offers: offers::Schema(view).index().keys().count() as u64,
+        coin_supply: if supply::Schema(view).exists() {
+            Some(supply::Schema(view).fetch())
+        } else {
+            None
+        },
+    };
+
+    println!("{}", serde_json::to_string_pretty(&summary).unwrap());
+}
@@ -1,37 +1,185 @@
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
 use std::io;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use exonum::crypto;
 use exonum::crypto::{PublicKey, SecretKey};
 use exonum::encoding::serialize::FromHex;
 
 use dmbc::config;
+use keystore;
+use passphrase::Passphrase;
 
-pub fn pair(name: &str) -> io::Result<(PublicKey, SecretKey)> {
+/// Names of the key pairs every node needs.
+pub const KEY_NAMES: &[&str] = &["consensus", "service"];
+
+fn public_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.pub", name))
+}
+
+fn invalid(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} does not contain a hex encoded key", path.display()),
+    )
+}
+
+/// Read the public key `name` from the key directory `dir`.
+pub fn public_key<P: AsRef<Path>>(dir: P, name: &str) -> io::Result<PublicKey> {
+    let path = public_path(dir.as_ref(), name);
+    let key_string = slurp(&path)?;
+    PublicKey::from_hex(key_string.trim()).map_err(|_| invalid(&path))
+}
+
+/// Read the key pair `name` from the configured key directory. Encrypted
+/// secret keys are opened with the passphrase; plaintext ones are still
+/// accepted until they are migrated with `dmbc-node encrypt-keys`.
+pub fn pair(name: &str, passphrase: &mut Passphrase) -> io::Result<(PublicKey, SecretKey)> {
     let keys_path = config::config().api().keys_path();
-    let public_path = keys_path.clone() + "/" + name + ".pub";
-    let secret_path = keys_path.clone() + "/" + name;
-
-    slurp(&public_path)
-        .map(|key_string| PublicKey::from_hex(key_string).unwrap())
-        .and_then(|public_key| {
-            let key_string = slurp(&secret_path)?;
-            let secret_key = SecretKey::from_hex(key_string).unwrap();
-            Ok((public_key, secret_key))
-        })
-        .or_else(|e| {
-            if e.kind() != io::ErrorKind::NotFound {
-                return Err(e);
+    let dir = Path::new(&keys_path);
+    let secret_path = dir.join(name);
+
+    let public_key = public_key(dir, name).map_err(|e| {
+        if e.kind() != io::ErrorKind::NotFound {
+            return e;
+        }
+        io::Error::new(
+            e.kind(),
+            format!(
+                "{} key not found in {}, create it with `dmbc-node generate-keys`",
+                name, keys_path
+            ),
+        )
+    })?;
+
+    let content = slurp(&secret_path)?;
+    let secret_key = if keystore::is_encrypted(&content) {
+        keystore::decrypt(&content, &passphrase.get(false)?).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", secret_path.display(), e),
+            )
+        })?
+    } else {
+        eprintln!(
+            "Warning: {} is not encrypted, run `dmbc-node encrypt-keys` to protect it",
+            secret_path.display()
+        );
+        plaintext_secret_key(&secret_path, &content)?
+    };
+    check_pair(&secret_path, &public_key, &secret_key)?;
+    Ok((public_key, secret_key))
+}
+
+/// Generate all key pairs in `dir`. Existing keys are kept unless `force`
+/// is set.
+pub fn generate<P: AsRef<Path>>(
+    dir: P,
+    force: bool,
+    passphrase: &mut Passphrase,
+) -> io::Result<Vec<(String, PublicKey)>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    if !force {
+        for name in KEY_NAMES {
+            let path = public_path(dir, name);
+            if path.exists() || dir.join(name).exists() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{} already exists, use --force to overwrite", path.display()),
+                ));
             }
-            let (public_key, secret_key) = crypto::gen_keypair();
-            let mut public_file = File::create(public_path)?;
-            let mut secret_file = File::create(secret_path)?;
-            write!(public_file, "{}", public_key.to_hex())?;
-            write!(secret_file, "{}", secret_key.to_hex())?;
-            Ok((public_key, secret_key))
-        })
+        }
+    }
+
+    let passphrase = passphrase.get(true)?;
+    let mut generated = Vec::new();
+    for name in KEY_NAMES {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        let mut public_file = File::create(public_path(dir, name))?;
+        write!(public_file, "{}", public_key.to_hex())?;
+        write_secret(&dir.join(name), &keystore::encrypt(&secret_key, &passphrase))?;
+        generated.push((name.to_string(), public_key));
+    }
+    Ok(generated)
+}
+
+/// Encrypt the plaintext secret keys in `dir` in place. Returns the names of
+/// the keys that were encrypted; keys that already are are skipped.
+pub fn encrypt_existing<P: AsRef<Path>>(
+    dir: P,
+    passphrase: &mut Passphrase,
+) -> io::Result<Vec<String>> {
+    let dir = dir.as_ref();
+
+    // Check every key before touching any of them.
+    let mut plaintext = Vec::new();
+    for name in KEY_NAMES {
+        let secret_path = dir.join(name);
+        let content = slurp(&secret_path)?;
+        if keystore::is_encrypted(&content) {
+            continue;
+        }
+        let secret_key = plaintext_secret_key(&secret_path, &content)?;
+        check_pair(&secret_path, &public_key(dir, name)?, &secret_key)?;
+        plaintext.push((name.to_string(), secret_path, secret_key));
+    }
+    if plaintext.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let passphrase = passphrase.get(true)?;
+    let mut encrypted = Vec::new();
+    for (name, secret_path, secret_key) in plaintext {
+        // Write next to the key and rename, so an interrupted migration
+        // never leaves a truncated key behind.
+        let temporary = dir.join(format!(".{}.tmp", name));
+        write_secret(&temporary, &keystore::encrypt(&secret_key, &passphrase))?;
+        fs::rename(&temporary, &secret_path)?;
+        encrypted.push(name);
+    }
+    Ok(encrypted)
+}
+
+fn plaintext_secret_key(path: &Path, content: &str) -> io::Result<SecretKey> {
+    SecretKey::from_hex(content.trim()).map_err(|_| invalid(path))
+}
+
+/// Ed25519 secret keys end with their public key.
+fn check_pair(path: &Path, public_key: &PublicKey, secret_key: &SecretKey) -> io::Result<()> {
+    if secret_key[32..] != public_key[..] {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} does not match its public key", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn write_secret(path: &Path, content: &str) -> io::Result<()> {
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    write!(file, "{}", content)?;
+    file.sync_all()
+}
+
+#[cfg(not(unix))]
+fn write_secret(path: &Path, content: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?;
+    write!(file, "{}", content)?;
+    file.sync_all()
 }
 
 fn slurp<P: AsRef<Path>>(filename: P) -> io::Result<String> {
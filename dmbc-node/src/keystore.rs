@@ -0,0 +1,119 @@
+//! Encrypted storage of secret keys.
+//!
+//! A secret key file holds JSON with the key sealed by XSalsa20-Poly1305
+//! under a key derived from the passphrase with scrypt. The KDF parameters
+//! are stored next to the ciphertext, so they can be raised later without
+//! breaking existing files. Files holding a bare hex key are the legacy
+//! plaintext format.
+
+use std::fmt;
+
+use exonum::crypto::SecretKey;
+use exonum::encoding::serialize::{encode_hex, FromHex};
+use serde_json;
+use sodiumoxide::crypto::pwhash::{self, MemLimit, OpsLimit, Salt};
+use sodiumoxide::crypto::secretbox::{self, Key, Nonce};
+
+/// Version of the keystore format.
+pub const VERSION: u16 = 1;
+
+const KDF: &str = "scrypt-salsa208-sha256";
+const CIPHER: &str = "xsalsa20-poly1305";
+
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    version: u16,
+    kdf: String,
+    salt: String,
+    opslimit: usize,
+    memlimit: usize,
+    cipher: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Reasons a secret key file can't be opened.
+#[derive(Debug)]
+pub enum Error {
+    /// The file is not a keystore this version can read.
+    Malformed(String),
+    /// The passphrase is wrong or the file was tampered with.
+    Decryption,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Malformed(ref reason) => write!(f, "malformed key file: {}", reason),
+            Error::Decryption => write!(f, "wrong passphrase or corrupted key file"),
+        }
+    }
+}
+
+fn derive(passphrase: &str, salt: &Salt, opslimit: usize, memlimit: usize) -> Result<Key, Error> {
+    let mut key = Key([0; secretbox::KEYBYTES]);
+    {
+        let Key(ref mut bytes) = key;
+        pwhash::derive_key(
+            bytes,
+            passphrase.as_bytes(),
+            salt,
+            OpsLimit(opslimit),
+            MemLimit(memlimit),
+        ).map_err(|_| Error::Malformed("unsupported KDF parameters".to_string()))?;
+    }
+    Ok(key)
+}
+
+/// Checks whether the file contents are in the encrypted format.
+pub fn is_encrypted(content: &str) -> bool {
+    content.trim_left().starts_with('{')
+}
+
+/// Seal the secret key with the passphrase.
+pub fn encrypt(secret_key: &SecretKey, passphrase: &str) -> String {
+    let salt = pwhash::gen_salt();
+    let OpsLimit(opslimit) = pwhash::OPSLIMIT_INTERACTIVE;
+    let MemLimit(memlimit) = pwhash::MEMLIMIT_INTERACTIVE;
+    let key = derive(passphrase, &salt, opslimit, memlimit).expect("Default KDF parameters");
+    let nonce = secretbox::gen_nonce();
+    let ciphertext = secretbox::seal(&secret_key[..], &nonce, &key);
+
+    let sealed = Sealed {
+        version: VERSION,
+        kdf: KDF.to_string(),
+        salt: encode_hex(&salt.0[..]),
+        opslimit,
+        memlimit,
+        cipher: CIPHER.to_string(),
+        nonce: encode_hex(&nonce.0[..]),
+        ciphertext: encode_hex(&ciphertext),
+    };
+    serde_json::to_string_pretty(&sealed).unwrap()
+}
+
+/// Open an encrypted secret key file.
+pub fn decrypt(content: &str, passphrase: &str) -> Result<SecretKey, Error> {
+    let sealed: Sealed =
+        serde_json::from_str(content).map_err(|e| Error::Malformed(e.to_string()))?;
+    if sealed.version != VERSION || sealed.kdf != KDF || sealed.cipher != CIPHER {
+        return Err(Error::Malformed(format!(
+            "unsupported format version {} ({}, {})",
+            sealed.version, sealed.kdf, sealed.cipher
+        )));
+    }
+
+    let hex = |field: &str, value: &str| {
+        Vec::<u8>::from_hex(value).map_err(|_| Error::Malformed(format!("invalid {}", field)))
+    };
+    let salt = Salt::from_slice(&hex("salt", &sealed.salt)?)
+        .ok_or_else(|| Error::Malformed("invalid salt length".to_string()))?;
+    let nonce = Nonce::from_slice(&hex("nonce", &sealed.nonce)?)
+        .ok_or_else(|| Error::Malformed("invalid nonce length".to_string()))?;
+    let ciphertext = hex("ciphertext", &sealed.ciphertext)?;
+
+    let key = derive(passphrase, &salt, sealed.opslimit, sealed.memlimit)?;
+    let plaintext = secretbox::open(&ciphertext, &nonce, &key).map_err(|_| Error::Decryption)?;
+    SecretKey::from_slice(&plaintext)
+        .ok_or_else(|| Error::Malformed("invalid secret key length".to_string()))
+}
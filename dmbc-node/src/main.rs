@@ -1,47 +1,169 @@
 extern crate curl;
 extern crate exonum;
 extern crate exonum_configuration;
-extern crate exonum_rocksdb;
+extern crate exonum_sodiumoxide as sodiumoxide;
+extern crate libc;
 extern crate serde;
+#[macro_use]
 extern crate serde_derive;
 extern crate clap;
+#[macro_use]
 extern crate serde_json;
 
 extern crate dmbc;
 
+mod audit;
+mod backup;
+mod db;
 mod flag;
+mod genesis;
+mod inspect;
 mod keyfile;
+mod keystore;
+mod passphrase;
+mod signals;
+mod state;
+
+use std::process;
+use std::time::Duration;
 
 use dmbc::config;
+use dmbc::currency::genesis::Genesis;
+use dmbc::currency::sinks;
 use dmbc::currency::Service;
+use dmbc::logging;
 use exonum::blockchain;
-use exonum::blockchain::{ConsensusConfig, GenesisConfig, TimeoutAdjusterConfig, ValidatorKeys};
-use exonum::crypto::PublicKey;
-use exonum::encoding::serialize::FromHex;
 use exonum::node::{Node, NodeApiConfig, NodeConfig};
-use exonum::storage::{RocksDB, RocksDBOptions};
+use exonum::storage::{Database, RocksDB};
 use exonum_configuration::ConfigurationService;
 
+use passphrase::Passphrase;
+
 pub const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
-const GENESIS_VALIDATOR_PUBLIC: &str =
-    "4e298e435018ab0a1430b6ebd0a0656be15493966d5ce86ed36416e24c411b9f";
-const GENESIS_SERVICE_PUBLIC: &str =
-    "68e774a4339cccfae644dcf3e44360839c84a6475c7d2943ed59b81d7eb6e9f0";
+/// Time given to event sinks to deliver queued events on shutdown.
+const SINK_FLUSH_SECONDS: u64 = 10;
+
+/// Print the error and exit with a failure status.
+fn fail<E: ::std::fmt::Display>(context: &str, e: E) -> ! {
+    eprintln!("{}: {}", context, e);
+    process::exit(1);
+}
 
 fn main() {
-    let _f = match flag::parse() {
+    let flags = match flag::parse() {
         Some(f) => f,
-        None => ::std::process::exit(0),
+        None => process::exit(0),
     };
 
-    exonum::helpers::init_logger().unwrap();
+    if let Err(errors) = config::init(&flags.config_path) {
+        eprintln!("Invalid configuration in {}:", flags.config_path);
+        for e in errors.0 {
+            eprintln!("  {}", e);
+        }
+        process::exit(1);
+    }
+
+    exonum::crypto::init();
+    let mut passphrase = Passphrase::new(flags.passphrase_file);
+
+    match flags.command {
+        flag::Command::Run {
+            genesis_path,
+            state_path,
+        } => run(genesis_path, state_path, &mut passphrase),
+        flag::Command::GenerateKeys { output, force } => {
+            generate_keys(output, force, &mut passphrase)
+        }
+        flag::Command::EncryptKeys { keys_path } => encrypt_keys(keys_path, &mut passphrase),
+        flag::Command::GenerateGenesis { validators, output } => {
+            generate_genesis(&validators, output)
+        }
+        flag::Command::InspectDb { db_path } => inspect::run(db_path),
+        flag::Command::ExportState { db_path, output } => state::export(db_path, output),
+        flag::Command::PrintConfig => print!("{}", config::config().to_toml()),
+        flag::Command::AuditAssets { db_path } => audit::run(db_path),
+        flag::Command::Backup { name, api } => backup::run(&name, api),
+        flag::Command::Restore {
+            checkpoint,
+            db_path,
+            force,
+        } => backup::restore(&checkpoint, db_path, force),
+    }
+}
+
+fn generate_keys(output: Option<String>, force: bool, passphrase: &mut Passphrase) {
+    let dir = output.unwrap_or_else(|| config::config().api().keys_path());
+    match keyfile::generate(&dir, force, passphrase) {
+        Ok(keys) => for (name, public_key) in keys {
+            println!("{} {}", name, public_key.to_hex());
+        },
+        Err(e) => fail(&format!("Unable to generate keys in {}", dir), e),
+    }
+}
+
+fn encrypt_keys(keys_path: Option<String>, passphrase: &mut Passphrase) {
+    let dir = keys_path.unwrap_or_else(|| config::config().api().keys_path());
+    match keyfile::encrypt_existing(&dir, passphrase) {
+        Ok(ref names) if names.is_empty() => println!("All keys in {} are encrypted", dir),
+        Ok(names) => for name in names {
+            println!("Encrypted {}", name);
+        },
+        Err(e) => fail(&format!("Unable to encrypt keys in {}", dir), e),
+    }
+}
+
+fn generate_genesis(validators: &[String], output: Option<String>) {
+    let genesis = match genesis::generate(validators) {
+        Ok(genesis) => genesis,
+        Err(e) => fail("Unable to read validator keys", e),
+    };
+    if let Err(e) = genesis::save(&genesis, output.as_ref().map(|p| p.as_str())) {
+        fail("Unable to write genesis configuration", e);
+    }
+}
+
+fn run(genesis_path: Option<String>, state_path: Option<String>, passphrase: &mut Passphrase) {
+    // Before any thread is started, so that all of them ignore the signals.
+    let signals = match signals::block() {
+        Ok(signals) => signals,
+        Err(e) => fail("Unable to set up signal handling", e),
+    };
+    if let Err(e) = logging::init(config::config().log()) {
+        fail("Unable to initialize logging", e);
+    }
+    if let Err(e) = sinks::start() {
+        fail("Unable to start event sinks", e);
+    }
 
-    /** Create Keys */
     println!("Initializing node version: v{}", VERSION);
 
-    let (consensus_public_key, consensus_secret_key) = keyfile::pair("consensus").unwrap();
-    let (service_public_key, service_secret_key) = keyfile::pair("service").unwrap();
+    let (consensus_public_key, consensus_secret_key) = match keyfile::pair("consensus", passphrase) {
+        Ok(pair) => pair,
+        Err(e) => fail("Unable to read consensus keys", e),
+    };
+    let (service_public_key, service_secret_key) = match keyfile::pair("service", passphrase) {
+        Ok(pair) => pair,
+        Err(e) => fail("Unable to read service keys", e),
+    };
+
+    let genesis = match genesis_path {
+        Some(path) => match genesis::load(&path) {
+            Ok(genesis) => genesis,
+            Err(e) => fail(&format!("Invalid genesis file {}", path), e),
+        },
+        None => Genesis {
+            consensus: config::config().consensus(),
+            ..Genesis::default()
+        },
+    };
+    if genesis.consensus != config::config().consensus() {
+        eprintln!("Using consensus parameters from the genesis file, [consensus] is ignored");
+    }
+    let dump = state_path.map(|path| match state::load(&path, &genesis) {
+        Ok(dump) => dump,
+        Err(e) => fail(&format!("Invalid state dump {}", path), e),
+    });
 
     let public_api = config::config().api().address();
     let private_api = config::config().api().private_address();
@@ -56,25 +178,10 @@ fn main() {
         }
     );
 
-    let consensus_config = ConsensusConfig {
-        round_timeout: 3500,
-        status_timeout: 5000,
-        peers_timeout: 10_000,
-        txs_block_limit: 3000,
-        max_message_len: ConsensusConfig::DEFAULT_MESSAGE_MAX_LEN,
-        timeout_adjuster: TimeoutAdjusterConfig::Constant { timeout: 2500 },
-    };
-
-    // Configure Node
-    let validators = Some(ValidatorKeys {
-        consensus_key: PublicKey::from_hex(GENESIS_VALIDATOR_PUBLIC).unwrap(),
-        service_key: PublicKey::from_hex(GENESIS_SERVICE_PUBLIC).unwrap(),
-    });
-
-    let genesis = GenesisConfig::new_with_consensus(consensus_config, validators.into_iter());
     let api_cfg = NodeApiConfig {
-        public_api_address: Some(public_api.parse().unwrap()),
-        private_api_address: Some(private_api.parse().unwrap()),
+        public_api_address: Some(public_api),
+        private_api_address: Some(private_api),
+        allow_origin: Some(config::config().api().allow_origin()),
         ..Default::default()
     };
 
@@ -82,40 +189,76 @@ fn main() {
 
     // Complete node configuration
     let node_cfg = NodeConfig {
-        listen_address: config::config().api().peer_address().parse().unwrap(),
+        listen_address: config::config().api().peer_address(),
         peers,
         service_public_key,
         service_secret_key,
         consensus_public_key,
         consensus_secret_key,
-        genesis,
+        genesis: genesis::core_config(&genesis),
         external_address: None,
-        network: Default::default(),
-        whitelist: Default::default(),
+        network: config::config().network(),
+        whitelist: config::config().whitelist(),
         api: api_cfg,
-        mempool: Default::default(),
+        mempool: config::config().mempool(),
         services_configs: Default::default(),
     };
 
     // Initialize database
-    let mut options = RocksDBOptions::default();
-    options.create_if_missing(true);
-    options.enable_statistics();
-    if cfg!(target_os = "linux") {
-        use exonum_rocksdb::DBCompressionType;
-        options.set_compression_type(DBCompressionType::Zlib);
-    }
     let path = config::config().db().path();
-    let db = Box::new(RocksDB::open(path, &options).unwrap());
+    let db = match db::open(&path) {
+        Ok(db) => db,
+        Err(e) => fail(&format!("Unable to open database at {}", path), e),
+    };
 
     // Initialize services
+    let mut service = Service::with_genesis(genesis).with_backups(db.clone());
+    if let Some(dump) = dump {
+        if blockchain::Schema::new(&db.snapshot()).block_hashes_by_height().len() > 0 {
+            eprintln!("The database already has a genesis block, --state is ignored");
+        } else {
+            eprintln!(
+                "Seeding genesis with the state at block #{} ({})",
+                dump.header.height,
+                dump.header.block_hash.to_hex()
+            );
+            service = service.with_state(dump);
+        }
+    }
     let services: Vec<Box<blockchain::Service>> = vec![
         Box::new(ConfigurationService::new()),
-        Box::new(Service::new()),
+        Box::new(service),
     ];
 
     eprintln!("Launching node. What can possibly go wrong?");
 
-    let node = Node::new(db, services, node_cfg);
-    node.run().unwrap();
+    let node = Node::new(db::boxed(db.clone()), services, node_cfg);
+    let api_sender = node.channel();
+    signals.handle(move |signal| {
+        eprintln!("Received {}, stopping the node", signals::name(signal));
+        if let Err(e) = api_sender.shutdown() {
+            fail("Unable to stop the node", e);
+        }
+    });
+
+    if let Err(e) = node.run() {
+        fail("Node failed", e);
+    }
+    shutdown(&db);
+}
+
+/// Deliver queued events and make committed blocks durable after the node
+/// has stopped.
+fn shutdown(db: &RocksDB) {
+    let mut status = 0;
+    if !sinks::flush(Duration::from_secs(SINK_FLUSH_SECONDS)) {
+        eprintln!("Event sinks did not finish within {} seconds", SINK_FLUSH_SECONDS);
+        status = 1;
+    }
+    if let Err(e) = db.sync() {
+        eprintln!("Unable to sync the database: {}", e);
+        status = 1;
+    }
+    eprintln!("Node stopped");
+    process::exit(status);
 }
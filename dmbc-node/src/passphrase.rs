@@ -0,0 +1,143 @@
+//! Keystore passphrase input.
+//!
+//! The passphrase is taken from the first available source:
+//!
+//! 1. the `DMBC_KEYS_PASSPHRASE` environment variable;
+//! 2. the file named by `--passphrase-file` or `DMBC_KEYS_PASSPHRASE_FILE`,
+//!    with trailing newlines removed;
+//! 3. a prompt on the terminal.
+//!
+//! An empty passphrase is rejected whatever its source.
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+
+/// Environment variable holding the passphrase itself.
+pub const PASSPHRASE_ENV: &str = "DMBC_KEYS_PASSPHRASE";
+
+/// Environment variable holding the path to a passphrase file.
+pub const PASSPHRASE_FILE_ENV: &str = "DMBC_KEYS_PASSPHRASE_FILE";
+
+/// Passphrase source, read once and reused for every key.
+pub struct Passphrase {
+    file: Option<String>,
+    cached: Option<String>,
+}
+
+impl Passphrase {
+    /// Passphrase read from `file` unless the environment variable is set.
+    pub fn new(file: Option<String>) -> Self {
+        Passphrase { file, cached: None }
+    }
+
+    /// Get the passphrase, reading it on first use. When prompting on the
+    /// terminal and `confirm` is set, the passphrase is asked twice and
+    /// must match.
+    pub fn get(&mut self, confirm: bool) -> io::Result<String> {
+        if self.cached.is_none() {
+            self.cached = Some(read(self.file.as_ref().map(|f| f.as_str()), confirm)?);
+        }
+        Ok(self.cached.clone().unwrap())
+    }
+}
+
+fn read(file: Option<&str>, confirm: bool) -> io::Result<String> {
+    let passphrase = read_source(file, confirm)?;
+    if passphrase.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty passphrase"));
+    }
+    Ok(passphrase)
+}
+
+fn read_source(file: Option<&str>, confirm: bool) -> io::Result<String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    let file = file
+        .map(|f| f.to_string())
+        .or_else(|| env::var(PASSPHRASE_FILE_ENV).ok());
+    if let Some(path) = file {
+        let mut content = String::new();
+        File::open(&path)?.read_to_string(&mut content)?;
+        return Ok(content.trim_right_matches(|c| c == '\n' || c == '\r').to_string());
+    }
+
+    let passphrase = prompt("Keystore passphrase: ")?;
+    if confirm && prompt("Repeat passphrase: ")? != passphrase {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "passphrases do not match"));
+    }
+    Ok(passphrase)
+}
+
+fn prompt(message: &str) -> io::Result<String> {
+    if !is_tty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no terminal to ask for the passphrase, set {} or {}",
+                PASSPHRASE_ENV, PASSPHRASE_FILE_ENV
+            ),
+        ));
+    }
+
+    eprint!("{}", message);
+    io::stderr().flush()?;
+    let _echo = EchoOff::new();
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    eprintln!();
+    Ok(line.trim_right_matches(|c| c == '\n' || c == '\r').to_string())
+}
+
+#[cfg(unix)]
+fn is_tty() -> bool {
+    unsafe { ::libc::isatty(::libc::STDIN_FILENO) == 1 }
+}
+
+#[cfg(not(unix))]
+fn is_tty() -> bool {
+    true
+}
+
+/// Disables terminal echo until dropped.
+#[cfg(unix)]
+struct EchoOff(Option<::libc::termios>);
+
+#[cfg(unix)]
+impl EchoOff {
+    fn new() -> Self {
+        unsafe {
+            let mut term: ::libc::termios = ::std::mem::zeroed();
+            if ::libc::tcgetattr(::libc::STDIN_FILENO, &mut term) != 0 {
+                return EchoOff(None);
+            }
+            let saved = term;
+            term.c_lflag &= !::libc::ECHO;
+            ::libc::tcsetattr(::libc::STDIN_FILENO, ::libc::TCSANOW, &term);
+            EchoOff(Some(saved))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        if let Some(ref saved) = self.0 {
+            unsafe {
+                ::libc::tcsetattr(::libc::STDIN_FILENO, ::libc::TCSANOW, saved);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct EchoOff;
+
+#[cfg(not(unix))]
+impl EchoOff {
+    fn new() -> Self {
+        EchoOff
+    }
+}
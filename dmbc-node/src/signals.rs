@@ -0,0 +1,70 @@
+//! Termination signals.
+//!
+//! SIGINT and SIGTERM are blocked before any thread is started, so every
+//! thread inherits the mask, and are received by a dedicated thread with
+//! `sigwait`. The first signal asks the node to stop; a second one exits
+//! at once.
+
+use std::io;
+use std::mem;
+use std::process;
+use std::thread;
+
+use libc;
+
+/// Signals that stop the node.
+pub struct Signals(libc::sigset_t);
+
+/// Block SIGINT and SIGTERM in the calling thread and the threads it
+/// starts afterwards.
+pub fn block() -> io::Result<Signals> {
+    unsafe {
+        let mut set: libc::sigset_t = mem::zeroed();
+        libc::sigemptyset(&mut set);
+        libc::sigaddset(&mut set, libc::SIGINT);
+        libc::sigaddset(&mut set, libc::SIGTERM);
+        match libc::pthread_sigmask(libc::SIG_BLOCK, &set, ::std::ptr::null_mut()) {
+            0 => Ok(Signals(set)),
+            e => Err(io::Error::from_raw_os_error(e)),
+        }
+    }
+}
+
+/// Name of a signal handled here.
+pub fn name(signal: i32) -> &'static str {
+    match signal {
+        libc::SIGINT => "SIGINT",
+        libc::SIGTERM => "SIGTERM",
+        _ => "signal",
+    }
+}
+
+impl Signals {
+    /// Call `on_signal` on the first signal received, and exit with the
+    /// conventional `128 + signal` status on the second one.
+    pub fn handle<F>(self, on_signal: F)
+    where
+        F: FnOnce(i32) + Send + 'static,
+    {
+        let set = self.0;
+        thread::Builder::new()
+            .name("signals".to_string())
+            .spawn(move || {
+                let signal = wait(&set);
+                on_signal(signal);
+                let signal = wait(&set);
+                eprintln!("Received {} again, exiting immediately", name(signal));
+                process::exit(128 + signal);
+            })
+            .unwrap();
+    }
+}
+
+fn wait(set: &libc::sigset_t) -> i32 {
+    let mut signal = 0;
+    loop {
+        if unsafe { libc::sigwait(set, &mut signal) } == 0 {
+            return signal;
+        }
+    }
+}
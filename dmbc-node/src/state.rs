@@ -0,0 +1,58 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter};
+use std::process;
+
+use exonum::storage::{Database, RocksDB, RocksDBOptions};
+
+use dmbc::config;
+use dmbc::currency::genesis::Genesis;
+use dmbc::currency::state::{self, Dump};
+
+/// Write the state at the last block to `output`, or to stdout.
+///
+/// Like `inspect-db`, the database is opened read-only, and the state is
+/// the one at the last block committed when it was opened.
+pub fn export(db_path: Option<String>, output: Option<String>) {
+    let path = db_path.unwrap_or_else(|| config::config().db().path());
+
+    let db = match RocksDB::open_read_only(&path, &RocksDBOptions::default()) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Unable to open database at {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+    let snapshot = db.snapshot();
+
+    let result = match output {
+        Some(ref output) => {
+            File::create(output).and_then(|f| state::export(snapshot.as_ref(), BufWriter::new(f)))
+        }
+        None => {
+            let stdout = io::stdout();
+            let lock = stdout.lock();
+            state::export(snapshot.as_ref(), BufWriter::new(lock))
+        }
+    };
+    match result {
+        Ok(header) => eprintln!(
+            "Exported state at block #{} ({})",
+            header.height,
+            header.block_hash.to_hex()
+        ),
+        Err(e) => {
+            eprintln!("Unable to export state: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Read a state dump and check that it can seed a chain with `genesis`.
+pub fn load(path: &str, genesis: &Genesis) -> io::Result<Dump> {
+    let dump = Dump::read(BufReader::new(File::open(path)?))?;
+    dump.validate(genesis).map_err(|errors| {
+        io::Error::new(io::ErrorKind::InvalidData, errors.join("; "))
+    })?;
+    Ok(dump)
+}
@@ -0,0 +1,231 @@
+extern crate exonum;
+extern crate serde_json;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use exonum::storage::{RocksDB, RocksDBOptions};
+
+const PASSPHRASE: &str = "cli test";
+
+fn node_binary() -> PathBuf {
+    // target/<profile>/deps/cli-<hash> -> target/<profile>/dmbc-node
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().join("dmbc-node")
+}
+
+/// A scratch directory with a configuration using `<dir>/keys`.
+fn setup(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("dmbc-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let content = format!(
+        r#"
+        [api]
+        keys_path = "{}"
+
+        [db]
+        path = "{}"
+        "#,
+        dir.join("keys").display(),
+        dir.join("db").display(),
+    );
+    fs::write(dir.join("config.toml"), content).unwrap();
+    dir
+}
+
+fn dmbc_node(dir: &Path) -> Command {
+    let mut command = Command::new(node_binary());
+    command
+        .arg("--config")
+        .arg(dir.join("config.toml"))
+        .env("DMBC_KEYS_PASSPHRASE", PASSPHRASE)
+        .env_remove("DMBC_KEYS_PASSPHRASE_FILE");
+    command
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Public keys printed by `generate-keys`, by key name.
+fn printed_keys(output: &Output) -> Vec<(String, String)> {
+    stdout(output)
+        .lines()
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next().unwrap().to_string();
+            let key = parts.next().unwrap().to_string();
+            (name, key)
+        })
+        .collect()
+}
+
+#[test]
+fn cli_print_config() {
+    let dir = setup("print-config");
+    let keys_line = format!("keys_path = \"{}\"", dir.join("keys").display());
+
+    let output = dmbc_node(&dir).arg("print-config").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains(&keys_line), "{}", stdout(&output));
+
+    // Global options may follow the subcommand.
+    let output = Command::new(node_binary())
+        .arg("print-config")
+        .arg("--config")
+        .arg(dir.join("config.toml"))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains(&keys_line), "{}", stdout(&output));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cli_generate_keys() {
+    let dir = setup("generate-keys");
+    let keys = dir.join("other-keys");
+
+    let output = dmbc_node(&dir)
+        .arg("generate-keys")
+        .arg("--output")
+        .arg(&keys)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let printed = printed_keys(&output);
+    let names: Vec<&str> = printed.iter().map(|&(ref name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["consensus", "service"]);
+    for &(ref name, ref key) in &printed {
+        let public = fs::read_to_string(keys.join(format!("{}.pub", name))).unwrap();
+        assert_eq!(&public, key);
+    }
+    assert!(!dir.join("keys").exists());
+
+    // Existing keys are kept unless forced.
+    let output = dmbc_node(&dir)
+        .arg("generate-keys")
+        .arg("-o")
+        .arg(&keys)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("--force"), "{}", stderr(&output));
+
+    let output = dmbc_node(&dir)
+        .arg("generate-keys")
+        .arg("-o")
+        .arg(&keys)
+        .arg("--force")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_ne!(printed_keys(&output), printed);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cli_generate_genesis() {
+    let dir = setup("generate-genesis");
+    let mut validators = Vec::new();
+    for name in &["a", "b"] {
+        let keys = dir.join(name);
+        let output = dmbc_node(&dir)
+            .arg("generate-keys")
+            .arg("--output")
+            .arg(&keys)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", stderr(&output));
+        validators.push((keys, printed_keys(&output)));
+    }
+
+    let genesis = dir.join("genesis.json");
+    let output = dmbc_node(&dir)
+        .arg("generate-genesis")
+        .arg(&validators[0].0)
+        .arg(&validators[1].0)
+        .arg("--output")
+        .arg(&genesis)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let genesis: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&genesis).unwrap()).unwrap();
+    let listed = genesis["validators"].as_array().unwrap();
+    assert_eq!(listed.len(), 2);
+    for (listed, &(_, ref printed)) in listed.iter().zip(validators.iter()) {
+        assert_eq!(listed["consensus_key"], printed[0].1.as_str());
+        assert_eq!(listed["service_key"], printed[1].1.as_str());
+    }
+
+    // Validators are required.
+    let output = dmbc_node(&dir).arg("generate-genesis").output().unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("<validators>"), "{}", stderr(&output));
+
+    // A directory without keys is reported.
+    let output = dmbc_node(&dir)
+        .arg("generate-genesis")
+        .arg(dir.join("missing"))
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Unable to read validator keys"), "{}", stderr(&output));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cli_inspect_db() {
+    let dir = setup("inspect-db");
+
+    let missing = dir.join("missing");
+    let output = dmbc_node(&dir)
+        .arg("inspect-db")
+        .arg("--db")
+        .arg(&missing)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("Unable to open database"), "{}", stderr(&output));
+    assert!(!missing.exists());
+
+    let db = dir.join("empty");
+    {
+        let mut options = RocksDBOptions::default();
+        options.create_if_missing(true);
+        RocksDB::open(&db, &options).unwrap();
+    }
+    let output = dmbc_node(&dir)
+        .arg("inspect-db")
+        .arg("--db")
+        .arg(&db)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    let summary: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(summary["height"], serde_json::Value::Null);
+    assert_eq!(summary["wallets"], 0);
+
+    // The configured database is used by default.
+    let output = dmbc_node(&dir).arg("inspect-db").output().unwrap();
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains(&dir.join("db").display().to_string()),
+        "{}",
+        stderr(&output)
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
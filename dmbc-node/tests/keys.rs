@@ -0,0 +1,179 @@
+extern crate exonum;
+extern crate serde_json;
+
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use exonum::crypto;
+
+const PASSPHRASE: &str = "keys test";
+
+fn node_binary() -> PathBuf {
+    // target/<profile>/deps/keys-<hash> -> target/<profile>/dmbc-node
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().join("dmbc-node")
+}
+
+/// A scratch directory with a configuration using `<dir>/keys`.
+fn setup(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("dmbc-keys-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let content = format!(
+        r#"
+        [api]
+        keys_path = "{}"
+
+        [db]
+        path = "{}"
+        "#,
+        dir.join("keys").display(),
+        dir.join("db").display(),
+    );
+    fs::write(dir.join("config.toml"), content).unwrap();
+    dir
+}
+
+fn dmbc_node(dir: &Path, passphrase: &str) -> Command {
+    let mut command = Command::new(node_binary());
+    command
+        .arg("--config")
+        .arg(dir.join("config.toml"))
+        .env("DMBC_KEYS_PASSPHRASE", passphrase)
+        .env_remove("DMBC_KEYS_PASSPHRASE_FILE");
+    command
+}
+
+/// Start the node with a missing genesis file, so that it stops right after
+/// reading its keys.
+fn read_keys(dir: &Path, passphrase: &str) -> Output {
+    dmbc_node(dir, passphrase)
+        .arg("run")
+        .arg("--genesis")
+        .arg(dir.join("missing.toml"))
+        .output()
+        .unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn assert_keys_read(output: &Output) {
+    let stderr = stderr(output);
+    assert!(!output.status.success());
+    assert!(!stderr.contains("Unable to read"), "{}", stderr);
+    assert!(stderr.contains("Invalid genesis file"), "{}", stderr);
+}
+
+fn assert_keys_rejected(output: &Output) {
+    let stderr = stderr(output);
+    assert!(!output.status.success());
+    assert!(stderr.contains("Unable to read consensus keys"), "{}", stderr);
+    assert!(stderr.contains("wrong passphrase or corrupted key file"), "{}", stderr);
+}
+
+#[test]
+fn keys_generated_encrypted() {
+    let dir = setup("generate");
+    let status = dmbc_node(&dir, PASSPHRASE).arg("generate-keys").status().unwrap();
+    assert!(status.success());
+
+    for name in &["consensus", "service"] {
+        let secret = dir.join("keys").join(name);
+        let content = fs::read_to_string(&secret).unwrap();
+        let sealed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(sealed["version"], 1);
+        let mode = fs::metadata(&secret).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    assert_keys_read(&read_keys(&dir, PASSPHRASE));
+    assert_keys_rejected(&read_keys(&dir, "wrong passphrase"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keys_tampered_ciphertext() {
+    let dir = setup("tampered");
+    let status = dmbc_node(&dir, PASSPHRASE).arg("generate-keys").status().unwrap();
+    assert!(status.success());
+
+    let secret = dir.join("keys").join("consensus");
+    let mut sealed: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&secret).unwrap()).unwrap();
+    let ciphertext = sealed["ciphertext"].as_str().unwrap().to_string();
+    let flipped = if ciphertext.starts_with('0') { "1" } else { "0" };
+    sealed["ciphertext"] = serde_json::Value::String(format!("{}{}", flipped, &ciphertext[1..]));
+    fs::write(&secret, sealed.to_string()).unwrap();
+
+    assert_keys_rejected(&read_keys(&dir, PASSPHRASE));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keys_encrypt_existing() {
+    let dir = setup("migrate");
+    let keys = dir.join("keys");
+    fs::create_dir_all(&keys).unwrap();
+    let mut public_keys = Vec::new();
+    for name in &["consensus", "service"] {
+        let (public_key, secret_key) = crypto::gen_keypair();
+        fs::write(keys.join(format!("{}.pub", name)), public_key.to_hex()).unwrap();
+        fs::write(keys.join(name), secret_key.to_hex()).unwrap();
+        public_keys.push(public_key.to_hex());
+    }
+
+    let output = dmbc_node(&dir, PASSPHRASE).arg("encrypt-keys").output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    for (name, public_key) in ["consensus", "service"].iter().zip(public_keys.iter()) {
+        let content = fs::read_to_string(keys.join(name)).unwrap();
+        assert!(content.trim_left().starts_with('{'));
+        let public = fs::read_to_string(keys.join(format!("{}.pub", name))).unwrap();
+        assert_eq!(&public, public_key);
+    }
+    assert!(!keys.join(".consensus.tmp").exists());
+
+    assert_keys_read(&read_keys(&dir, PASSPHRASE));
+    assert_keys_rejected(&read_keys(&dir, "wrong passphrase"));
+
+    // Encrypted keys are left alone.
+    let before = fs::read_to_string(keys.join("consensus")).unwrap();
+    let status = dmbc_node(&dir, "other").arg("encrypt-keys").status().unwrap();
+    assert!(status.success());
+    assert_eq!(fs::read_to_string(keys.join("consensus")).unwrap(), before);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keys_empty_passphrase() {
+    let dir = setup("empty");
+
+    let output = dmbc_node(&dir, "").arg("generate-keys").output().unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("empty passphrase"), "{}", stderr(&output));
+
+    let file = dir.join("passphrase");
+    fs::write(&file, "\n").unwrap();
+    let output = Command::new(node_binary())
+        .arg("--config")
+        .arg(dir.join("config.toml"))
+        .arg("--passphrase-file")
+        .arg(&file)
+        .env_remove("DMBC_KEYS_PASSPHRASE")
+        .arg("generate-keys")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("empty passphrase"), "{}", stderr(&output));
+    assert!(!dir.join("keys").join("consensus").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,151 @@
+extern crate curl;
+extern crate exonum;
+extern crate libc;
+
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use curl::easy::Easy;
+use exonum::blockchain;
+use exonum::storage::{Database, RocksDB, RocksDBOptions};
+
+const PASSPHRASE: &str = "shutdown test";
+
+fn node_binary() -> PathBuf {
+    // target/<profile>/deps/shutdown-<hash> -> target/<profile>/dmbc-node
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().join("dmbc-node")
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+fn dmbc_node(config: &Path) -> Command {
+    let mut command = Command::new(node_binary());
+    command
+        .arg("--config")
+        .arg(config)
+        .env("DMBC_KEYS_PASSPHRASE", PASSPHRASE);
+    command
+}
+
+fn get_status(url: &str) -> Option<u32> {
+    let mut easy = Easy::new();
+    easy.url(url).ok()?;
+    easy.write_function(|data| Ok(data.len())).ok()?;
+    easy.perform().ok()?;
+    easy.response_code().ok()
+}
+
+fn wait_timeout(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if let Some(status) = child.try_wait().unwrap() {
+            return Some(status);
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    None
+}
+
+#[test]
+fn node_stops_on_sigterm() {
+    let dir = env::temp_dir().join(format!("dmbc-shutdown-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let public_port = free_port();
+    let config = dir.join("config.toml");
+    let content = format!(
+        r#"
+        [api]
+        address = "127.0.0.1:{}"
+        private_address = "127.0.0.1:{}"
+        peer_address = "127.0.0.1:{}"
+        keys_path = "{}"
+
+        [db]
+        path = "{}"
+
+        [consensus]
+        round_timeout = 500
+        status_timeout = 1000
+
+        [consensus.timeout_adjuster]
+        type = "Constant"
+        timeout = 100
+        "#,
+        public_port,
+        free_port(),
+        free_port(),
+        dir.join("keys").display(),
+        dir.join("db").display(),
+    );
+    fs::write(&config, content).unwrap();
+
+    let status = dmbc_node(&config).arg("generate-keys").status().unwrap();
+    assert!(status.success());
+    let genesis = dir.join("genesis.toml");
+    let status = dmbc_node(&config)
+        .arg("generate-genesis")
+        .arg(dir.join("keys"))
+        .arg("--output")
+        .arg(&genesis)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut node = dmbc_node(&config)
+        .arg("run")
+        .arg("--genesis")
+        .arg(&genesis)
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    // Wait for the first block after the genesis one.
+    let block = format!(
+        "http://127.0.0.1:{}/api/services/cryptocurrency/v1/blocks/1",
+        public_port
+    );
+    let deadline = Instant::now() + Duration::from_secs(60);
+    while get_status(&block) != Some(200) {
+        assert!(Instant::now() < deadline, "the node did not commit a block");
+        assert!(node.try_wait().unwrap().is_none(), "the node exited");
+        thread::sleep(Duration::from_millis(200));
+    }
+
+    unsafe {
+        libc::kill(node.id() as libc::pid_t, libc::SIGTERM);
+    }
+    let status = match wait_timeout(&mut node, Duration::from_secs(30)) {
+        Some(status) => status,
+        None => {
+            node.kill().unwrap();
+            panic!("the node did not stop on SIGTERM");
+        }
+    };
+    assert!(status.success(), "the node exited with {}", status);
+
+    // The database is released and holds the committed blocks.
+    {
+        let mut options = RocksDBOptions::default();
+        options.create_if_missing(false);
+        let db = RocksDB::open(dir.join("db"), &options).unwrap();
+        let snapshot = db.snapshot();
+        let schema = blockchain::Schema::new(snapshot.as_ref());
+        assert!(schema.block_hashes_by_height().len() >= 2);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
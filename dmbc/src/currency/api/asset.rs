@@ -8,7 +8,6 @@ extern crate serde_json;
 use exonum::api::Api;
 use exonum::blockchain::Blockchain;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use prometheus::IntCounter;
@@ -59,7 +58,6 @@ impl Api for AssetApi {
                 serde_json::to_string_pretty(&a).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             INFO_RESPONSES.inc();
 
@@ -11,7 +11,6 @@ use exonum::api::Api;
 use exonum::crypto::PublicKey;
 use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use percent_encoding::percent_decode;
@@ -88,7 +87,6 @@ impl Api for AssetInternApi {
                 serde_json::to_string_pretty(&result).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
@@ -128,7 +126,6 @@ impl Api for AssetInternApi {
                 serde_json::to_string_pretty(&result).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
@@ -176,7 +173,6 @@ impl Api for AssetInternApi {
                 serde_json::to_string_pretty(&result).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
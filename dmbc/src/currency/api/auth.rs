@@ -0,0 +1,96 @@
+use hyper::method::Method;
+use iron::prelude::*;
+use iron::BeforeMiddleware;
+
+use currency::api::error::ApiError;
+
+/// Header carrying an API key, as an alternative to `Authorization: Bearer`.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Requires one of the configured keys on every request, except `OPTIONS`
+/// preflight requests. `GET` and `HEAD` requests and the others can be given
+/// different keys; a request whose group has no keys passes unchecked.
+///
+/// The key is taken from `Authorization: Bearer <key>` or from `X-API-Key`.
+#[derive(Clone, Debug)]
+pub struct ApiKeyAuth {
+    read_keys: Vec<String>,
+    write_keys: Vec<String>,
+}
+
+impl ApiKeyAuth {
+    /// Accept `keys` on every request.
+    pub fn new(keys: Vec<String>) -> Self {
+        ApiKeyAuth {
+            read_keys: keys.clone(),
+            write_keys: keys,
+        }
+    }
+
+    /// Accept `read_keys` on `GET` and `HEAD` requests and `write_keys` on
+    /// the others.
+    pub fn by_method(read_keys: Vec<String>, write_keys: Vec<String>) -> Self {
+        ApiKeyAuth {
+            read_keys,
+            write_keys,
+        }
+    }
+
+    fn keys(&self, method: &Method) -> &[String] {
+        match *method {
+            Method::Options => &[],
+            Method::Get | Method::Head => &self.read_keys,
+            _ => &self.write_keys,
+        }
+    }
+}
+
+/// Key presented with the request, if any.
+fn presented_key(req: &Request) -> Option<String> {
+    let header = |name: &str| {
+        req.headers
+            .get_raw(name)
+            .and_then(|values| values.first())
+            .and_then(|value| String::from_utf8(value.clone()).ok())
+    };
+    if let Some(authorization) = header("Authorization") {
+        let mut parts = authorization.trim().splitn(2, ' ');
+        if let (Some(scheme), Some(token)) = (parts.next(), parts.next()) {
+            if scheme.eq_ignore_ascii_case("Bearer") {
+                return Some(token.trim().to_string());
+            }
+        }
+    }
+    header(API_KEY_HEADER).map(|key| key.trim().to_string())
+}
+
+/// Compare keys in time independent of where they differ.
+fn same_key(a: &str, b: &str) -> bool {
+    let diff = a.bytes()
+        .zip(b.bytes())
+        .fold(0, |diff, (x, y)| diff | (x ^ y));
+    a.len() == b.len() && diff == 0
+}
+
+impl BeforeMiddleware for ApiKeyAuth {
+    fn before(&self, req: &mut Request) -> IronResult<()> {
+        let keys = self.keys(&req.method);
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let accepted = match presented_key(req) {
+            Some(ref key) => keys.iter().any(|k| same_key(k, key)),
+            None => false,
+        };
+        if accepted {
+            return Ok(());
+        }
+
+        let mut error = IronError::from(ApiError::Unauthorized);
+        error
+            .response
+            .headers
+            .set_raw("WWW-Authenticate", vec![b"Bearer".to_vec()]);
+        Err(error)
+    }
+}
@@ -0,0 +1,83 @@
+extern crate bodyparser;
+extern crate serde_json;
+
+use std::fs;
+use std::path::PathBuf;
+
+use exonum::api::Api;
+use exonum::storage::RocksDB;
+use hyper::header::ContentType;
+use iron::prelude::*;
+use iron::status;
+use router::Router;
+
+use currency::api::error::ApiError;
+use currency::backup;
+use currency::backup::Checkpoint;
+
+/// Checkpoints of the node database, written to `backup_dir`. Wired on the
+/// private API only.
+#[derive(Clone)]
+pub struct BackupApi {
+    pub database: RocksDB,
+    pub backup_dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BackupRequest {
+    /// Name of the checkpoint directory inside the backup directory.
+    pub name: String,
+}
+
+pub type BackupResponse = Result<Checkpoint, ApiError>;
+
+impl BackupApi {
+    fn backup(&self, name: &str) -> BackupResponse {
+        if !is_checkpoint_name(name) {
+            return Err(ApiError::BackupNameInvalid);
+        }
+        let path = self.backup_dir.join(name);
+        if path.exists() {
+            return Err(ApiError::BackupExists);
+        }
+        fs::create_dir_all(&self.backup_dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| backup::create(&self.database, &path).map_err(|e| e.to_string()))
+            .map_err(|e| {
+                error!("Backup to {} failed: {}", path.display(), e);
+                ApiError::BackupFailed
+            })
+    }
+}
+
+impl Api for BackupApi {
+    fn wire(&self, router: &mut Router) {
+        let self_ = self.clone();
+        let backup = move |req: &mut Request| -> IronResult<Response> {
+            let result: BackupResponse = match req.get::<bodyparser::Struct<BackupRequest>>() {
+                Ok(Some(request)) => self_.backup(&request.name),
+                Ok(None) => Err(ApiError::EmptyRequestBody),
+                Err(_) => Err(ApiError::IncorrectRequest),
+            };
+
+            let code = match result {
+                Ok(_) => status::Ok,
+                Err(ref e) => e.to_status(),
+            };
+            let mut res = Response::with((code, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+            Ok(res)
+        };
+
+        router.post("/v1/backup", backup, "backup");
+    }
+}
+
+/// Checks that `name` names an entry of the backup directory and can't
+/// point anywhere else.
+pub fn is_checkpoint_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && !name.contains("..")
+        && !name.contains(|c: char| c == '/' || c == '\\' || c.is_control())
+}
@@ -7,7 +7,6 @@ use exonum::blockchain::{Block, Blockchain};
 use exonum::explorer::{BlockInfo, BlockchainExplorer};
 use exonum::helpers::Height;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use prometheus::IntCounter;
@@ -110,7 +109,6 @@ impl Api for BlocksApi {
             let mut res =
                 Response::with((status_code, serde_json::to_string_pretty(&result).unwrap()));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             LIST_RESPONSES.inc();
             Ok(res)
@@ -137,7 +135,6 @@ impl Api for BlocksApi {
             let body = serde_json::to_string_pretty(&result).unwrap();
             let mut res = Response::with((status_code, body));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             INFO_RESPONSES.inc();
             Ok(res)
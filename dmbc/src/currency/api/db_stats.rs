@@ -1,7 +1,6 @@
 use exonum::api::Api;
 use exonum_rocksdb::Options;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use router::Router;
@@ -16,13 +15,11 @@ impl Api for DbStatsApi {
                 Some(stat_string) => {
                     let mut res = Response::with((status::Ok, stat_string));
                     res.headers.set(ContentType::plaintext());
-                    res.headers.set(AccessControlAllowOrigin::Any);
                     res
                 }
                 None => {
                     let mut res = Response::with((status::ServiceUnavailable, ""));
                     res.headers.set(ContentType::plaintext());
-                    res.headers.set(AccessControlAllowOrigin::Any);
                     res
                 }
             };
@@ -1,4 +1,10 @@
+use std::error::Error;
+use std::fmt;
+
+use hyper::header::ContentType;
 use hyper::status::StatusCode;
+use iron::prelude::*;
+use serde_json;
 
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ApiError {
@@ -11,6 +17,16 @@ pub enum ApiError {
     WalletHexInvalid,
     HeightIsMissing,
     BlockNotFound,
+    BatchTooLarge,
+    TxTypeInvalid,
+    TooManySubscribers,
+    BackupExists,
+    BackupFailed,
+    BackupNameInvalid,
+    HistoryUnavailable,
+    Unauthorized,
+    TooManyRequests,
+    RequestBodyTooLarge,
 }
 
 impl ApiError {
@@ -25,6 +41,49 @@ impl ApiError {
             ApiError::WalletHexInvalid => StatusCode::BadRequest,
             ApiError::HeightIsMissing => StatusCode::BadRequest,
             ApiError::BlockNotFound => StatusCode::NotFound,
+            ApiError::BatchTooLarge => StatusCode::PayloadTooLarge,
+            ApiError::TxTypeInvalid => StatusCode::BadRequest,
+            ApiError::TooManySubscribers => StatusCode::ServiceUnavailable,
+            ApiError::BackupExists => StatusCode::Conflict,
+            ApiError::BackupFailed => StatusCode::InternalServerError,
+            ApiError::BackupNameInvalid => StatusCode::BadRequest,
+            ApiError::HistoryUnavailable => StatusCode::NotFound,
+            ApiError::Unauthorized => StatusCode::Unauthorized,
+            ApiError::TooManyRequests => StatusCode::TooManyRequests,
+            ApiError::RequestBodyTooLarge => StatusCode::PayloadTooLarge,
+        }
+    }
+
+    /// Response rejecting a request with this error, with the body handlers
+    /// return for it.
+    pub fn to_response(&self) -> Response {
+        let result: Result<(), ApiError> = Err(*self);
+        let mut res = Response::with((
+            self.to_status(),
+            serde_json::to_string_pretty(&result).unwrap(),
+        ));
+        res.headers.set(ContentType::json());
+        res
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Error for ApiError {
+    fn description(&self) -> &str {
+        "currency API error"
+    }
+}
+
+impl From<ApiError> for IronError {
+    fn from(e: ApiError) -> IronError {
+        IronError {
+            response: e.to_response(),
+            error: Box::new(e),
         }
     }
 }
@@ -0,0 +1,135 @@
+extern crate serde_json;
+
+use std::io::{self, Write};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use exonum::api::Api;
+use exonum::crypto::PublicKey;
+use exonum::encoding::serialize::FromHex;
+use hyper::header::{CacheControl, CacheDirective, ContentType};
+use iron::prelude::*;
+use iron::response::WriteBody;
+use iron::status;
+use prometheus::{IntCounter, IntGauge};
+use router::Router;
+
+use currency::api::error::ApiError;
+use currency::api::ServiceApi;
+use currency::assets::AssetId;
+use currency::events;
+use currency::events::{Event, Filter, TxType};
+
+pub const PARAMETER_WALLET_KEY: &str = "wallet";
+pub const PARAMETER_ASSET_KEY: &str = "asset";
+pub const PARAMETER_TX_TYPE_KEY: &str = "tx_type";
+
+/// Interval between comments sent to idle subscribers. Writing them is also
+/// how disconnected clients get noticed.
+const KEEP_ALIVE_SECONDS: u64 = 15;
+
+/// Server-Sent Events stream of committed blocks and transactions.
+#[derive(Clone)]
+pub struct EventsApi {}
+
+pub type SubscribeResponse = Result<(), ApiError>;
+
+lazy_static! {
+    static ref SUBSCRIBE_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_events_api_subscribe_requests_total",
+        "Event stream subscription requests."
+    ).unwrap();
+    static ref SUBSCRIBERS: IntGauge = register_int_gauge!(
+        "dmbc_events_api_subscribers",
+        "Currently connected event stream subscribers."
+    ).unwrap();
+}
+
+/// Parse the subscription filter from the query parameters.
+pub fn parse_filter(
+    wallet: &str,
+    asset: &str,
+    tx_type: &str,
+) -> Result<Filter, ApiError> {
+    let mut filter = Filter::default();
+    if !wallet.is_empty() {
+        filter.wallet = Some(PublicKey::from_hex(wallet).map_err(|_| ApiError::WalletHexInvalid)?);
+    }
+    if !asset.is_empty() {
+        filter.asset = Some(AssetId::from_hex(asset).map_err(|_| ApiError::AssetIdInvalid)?);
+    }
+    if !tx_type.is_empty() {
+        filter.tx_type = Some(TxType::from_name(tx_type).ok_or(ApiError::TxTypeInvalid)?);
+    }
+    Ok(filter)
+}
+
+struct EventStream {
+    receiver: Receiver<Event>,
+}
+
+impl EventStream {
+    fn write_event(res: &mut Write, event: &Event) -> io::Result<()> {
+        let data = serde_json::to_string(event).unwrap();
+        write!(res, "event: {}\ndata: {}\n\n", event.name(), data)
+    }
+}
+
+impl WriteBody for EventStream {
+    fn write_body(&mut self, res: &mut Write) -> io::Result<()> {
+        SUBSCRIBERS.inc();
+        let keep_alive = Duration::from_secs(KEEP_ALIVE_SECONDS);
+        let result = loop {
+            let written = match self.receiver.recv_timeout(keep_alive) {
+                Ok(event) => EventStream::write_event(res, &event),
+                Err(RecvTimeoutError::Timeout) => res.write_all(b": keep-alive\n\n"),
+                Err(RecvTimeoutError::Disconnected) => break Ok(()),
+            };
+            if let Err(e) = written.and_then(|_| res.flush()) {
+                break Err(e);
+            }
+        };
+        SUBSCRIBERS.dec();
+        result
+    }
+}
+
+impl Api for EventsApi {
+    fn wire(&self, router: &mut Router) {
+        let subscribe = move |req: &mut Request| -> IronResult<Response> {
+            SUBSCRIBE_REQUESTS.inc();
+
+            let wallet = ServiceApi::read_parameter(req, PARAMETER_WALLET_KEY, String::new());
+            let asset = ServiceApi::read_parameter(req, PARAMETER_ASSET_KEY, String::new());
+            let tx_type = ServiceApi::read_parameter(req, PARAMETER_TX_TYPE_KEY, String::new());
+
+            let receiver = parse_filter(&wallet, &asset, &tx_type).and_then(|filter| {
+                events::subscribe(filter).ok_or(ApiError::TooManySubscribers)
+            });
+
+            let res = match receiver {
+                Ok(receiver) => {
+                    let mut res = Response::with(status::Ok);
+                    res.headers
+                        .set(ContentType("text/event-stream".parse().unwrap()));
+                    res.headers.set(CacheControl(vec![CacheDirective::NoCache]));
+                    res.body = Some(Box::new(EventStream { receiver }));
+                    res
+                }
+                Err(e) => {
+                    let result: SubscribeResponse = Err(e);
+                    let mut res = Response::with((
+                        e.to_status(),
+                        serde_json::to_string_pretty(&result).unwrap(),
+                    ));
+                    res.headers.set(ContentType::json());
+                    res
+                }
+            };
+
+            Ok(res)
+        };
+
+        router.get("/v1/events", subscribe, "events");
+    }
+}
@@ -12,16 +12,16 @@ use exonum::blockchain::Blockchain;
 use exonum::crypto::PublicKey;
 // use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use router::Router;
 
 use currency::api::error::ApiError;
 use currency::error::Error;
-use currency::transactions::components::FeesCalculator;
+use currency::transactions::components::{fees_by_payer, FeeItem, FeesCalculator};
 use currency::transactions::{
-    AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade, TradeIntermediary, Transfer,
+    AddAssets, AskOffer, BidOffer, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
+    TradeIntermediary, Transfer, TransferWithFeesPayer,
 };
 
 #[derive(Clone)]
@@ -33,24 +33,30 @@ pub struct FeesApi {
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum FeesRequest {
     Transfer(Transfer),
+    TransferWithFeesPayer(TransferWithFeesPayer),
     AddAssets(AddAssets),
     DeleteAssets(DeleteAssets),
     Trade(Trade),
     TradeIntermediary(TradeIntermediary),
     Exchange(Exchange),
     ExchangeIntermediary(ExchangeIntermediary),
+    BidOffer(BidOffer),
+    AskOffer(AskOffer),
 }
 
 impl Into<Box<FeesCalculator>> for FeesRequest {
     fn into(self) -> Box<FeesCalculator> {
         match self {
             FeesRequest::Transfer(trans) => Box::new(trans),
+            FeesRequest::TransferWithFeesPayer(trans) => Box::new(trans),
             FeesRequest::AddAssets(trans) => Box::new(trans),
             FeesRequest::DeleteAssets(trans) => Box::new(trans),
             FeesRequest::Trade(trans) => Box::new(trans),
             FeesRequest::TradeIntermediary(trans) => Box::new(trans),
             FeesRequest::Exchange(trans) => Box::new(trans),
             FeesRequest::ExchangeIntermediary(trans) => Box::new(trans),
+            FeesRequest::BidOffer(trans) => Box::new(trans),
+            FeesRequest::AskOffer(trans) => Box::new(trans),
         }
     }
 }
@@ -58,6 +64,7 @@ impl Into<Box<FeesCalculator>> for FeesRequest {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct FeesResponseBody {
     pub fees: HashMap<PublicKey, u64>,
+    pub items: Vec<FeeItem>,
 }
 
 pub type FeesResponse = Result<Result<FeesResponseBody, Error>, ApiError>;
@@ -70,8 +77,11 @@ impl Api for FeesApi {
                 Ok(Some(request)) => {
                     let calculator: Box<FeesCalculator> = request.into();
                     let view = &mut self_.blockchain.fork();
-                    match calculator.calculate_fees(view) {
-                        Ok(fees) => Ok(Ok(FeesResponseBody { fees })),
+                    match calculator.itemize_fees(view) {
+                        Ok(items) => Ok(Ok(FeesResponseBody {
+                            fees: fees_by_payer(&items),
+                            items,
+                        })),
                         Err(e) => Ok(Err(e)),
                     }
                 }
@@ -88,7 +98,6 @@ impl Api for FeesApi {
             let mut res =
                 Response::with((status_code, serde_json::to_string_pretty(&result).unwrap()));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
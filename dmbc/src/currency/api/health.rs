@@ -0,0 +1,176 @@
+extern crate serde_json;
+
+use std::time::SystemTime;
+
+use exonum::api::Api;
+use exonum::blockchain::{self, Blockchain, SharedNodeState};
+use exonum::crypto::PublicKey;
+use exonum::node::TxPool;
+use hyper::header::ContentType;
+use iron::prelude::*;
+use iron::status;
+use prometheus::IntCounter;
+use router::Router;
+
+use config;
+use currency::api::error::ApiError;
+use currency::configuration::Configuration;
+use currency::service::CONFIGURATION;
+use currency::sinks;
+
+#[derive(Clone)]
+pub struct HealthApi {
+    pub blockchain: Blockchain,
+    pub tx_pool: Option<TxPool>,
+    pub node_state: Option<SharedNodeState>,
+    pub service_key: PublicKey,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NatsHealth {
+    pub enabled: bool,
+    /// Outcome of the last attempt to reach NATS, `None` before any.
+    pub connected: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct HealthReport {
+    pub ready: bool,
+    pub height: u64,
+    /// Milliseconds since the last block was agreed on, `None` for the
+    /// genesis block.
+    pub last_block_age_ms: Option<u64>,
+    pub is_validator: bool,
+    pub peers: Option<usize>,
+    pub mempool_size: Option<usize>,
+    /// Highest height reported by the peers.
+    pub net_height: Option<u64>,
+    pub lag: u64,
+    pub max_lag: u64,
+    pub configuration_current: bool,
+    pub nats: NatsHealth,
+}
+
+pub type HealthResponse = Result<HealthReport, ApiError>;
+
+impl HealthApi {
+    fn report(&self) -> HealthReport {
+        let view = self.blockchain.snapshot();
+        let schema = blockchain::Schema::new(view.as_ref());
+
+        let last_block = schema.last_block();
+        let height = last_block.height().0;
+        let last_block_age_ms = schema
+            .precommits(&last_block.hash())
+            .iter()
+            .map(|precommit| precommit.time())
+            .max()
+            .and_then(|time| SystemTime::now().duration_since(time).ok())
+            .map(|age| age.as_secs() * 1000 + u64::from(age.subsec_nanos()) / 1_000_000);
+
+        let is_validator = schema
+            .actual_configuration()
+            .validator_keys
+            .iter()
+            .any(|keys| keys.service_key == self.service_key);
+
+        let peers = self.node_state
+            .as_ref()
+            .map(|state| state.peers_info().len());
+        let mempool_size = self.tx_pool
+            .as_ref()
+            .map(|pool| pool.read().unwrap().len());
+        // Peers report the height they work on, one above their last block.
+        let net_height = self.node_state
+            .as_ref()
+            .map(|state| state.net_height.read().unwrap().0.saturating_sub(1));
+        let lag = net_height.map_or(0, |net_height| net_height.saturating_sub(height));
+        let max_lag = config::config().health().max_lag();
+
+        let configuration_current =
+            *CONFIGURATION.read().unwrap() == Configuration::extract(view.as_ref());
+
+        let nats = NatsHealth {
+            enabled: config::config().nats().enabled(),
+            connected: sinks::nats::connected(),
+        };
+
+        HealthReport {
+            ready: lag <= max_lag && configuration_current,
+            height,
+            last_block_age_ms,
+            is_validator,
+            peers,
+            mempool_size,
+            net_height,
+            lag,
+            max_lag,
+            configuration_current,
+            nats,
+        }
+    }
+}
+
+lazy_static! {
+    static ref HEALTH_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_health_api_health_requests_total",
+        "Health requests."
+    ).unwrap();
+    static ref HEALTH_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_health_api_health_responses_total",
+        "Health responses."
+    ).unwrap();
+    static ref READY_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_health_api_ready_requests_total",
+        "Readiness requests."
+    ).unwrap();
+    static ref READY_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_health_api_ready_responses_total",
+        "Readiness responses."
+    ).unwrap();
+}
+
+impl Api for HealthApi {
+    fn wire(&self, router: &mut Router) {
+        // Reports the state of the node, always with 200 OK.
+        let self_ = self.clone();
+        let health = move |_: &mut Request| -> IronResult<Response> {
+            HEALTH_REQUESTS.inc();
+
+            let result: HealthResponse = Ok(self_.report());
+
+            let mut res =
+                Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+
+            HEALTH_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        // Same report, with 503 Service Unavailable while the node lags
+        // behind its peers or hasn't applied the current configuration.
+        let self_ = self.clone();
+        let ready = move |_: &mut Request| -> IronResult<Response> {
+            READY_REQUESTS.inc();
+
+            let report = self_.report();
+            let code = if report.ready {
+                status::Ok
+            } else {
+                status::ServiceUnavailable
+            };
+            let result: HealthResponse = Ok(report);
+
+            let mut res = Response::with((code, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+
+            READY_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        router.get("/v1/health", health, "health");
+        router.get("/v1/ready", ready, "ready");
+    }
+}
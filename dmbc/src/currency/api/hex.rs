@@ -9,7 +9,6 @@ use exonum::api::Api;
 use exonum::blockchain::Transaction;
 use exonum::messages::Message;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status as istatus;
 use router::Router;
@@ -92,7 +91,6 @@ impl Api for HexApi {
                 serde_json::to_string_pretty(&body).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
@@ -147,7 +145,6 @@ impl Api for HexApi {
                 serde_json::to_string_pretty(&body).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
@@ -10,7 +10,6 @@ use exonum::blockchain::Blockchain;
 use exonum::crypto::Hash;
 use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use prometheus::IntCounter;
@@ -111,7 +110,6 @@ impl Api for HistoryOffersApi {
             let mut res =
                 Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             LIST_RESPONSES.inc();
 
@@ -143,7 +141,6 @@ impl Api for HistoryOffersApi {
             let body = serde_json::to_string_pretty(&result).unwrap();
             let mut res = Response::with((status_code, body));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             INFO_RESPONSES.inc();
             Ok(res)
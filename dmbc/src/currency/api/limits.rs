@@ -0,0 +1,284 @@
+extern crate bodyparser;
+extern crate persistent;
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::str;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use hyper::header::ContentLength;
+use iron::prelude::*;
+use iron::BeforeMiddleware;
+use prometheus::IntCounter;
+
+use self::persistent::Read;
+use config::Rate;
+use currency::api::error::ApiError;
+
+/// Buckets kept before the oldest ones are forgotten, full or not.
+const MAX_BUCKETS: usize = 100_000;
+
+/// Buckets checked on each request, and forgotten once full again.
+const SWEEP_STEP: usize = 2;
+
+lazy_static! {
+    static ref RATE_LIMITED: IntCounter = register_int_counter!(
+        "dmbc_api_rate_limited_requests_total",
+        "Requests rejected for exceeding a rate limit."
+    ).unwrap();
+    static ref BODY_TOO_LARGE: IntCounter = register_int_counter!(
+        "dmbc_api_body_too_large_requests_total",
+        "Requests rejected for the size of their body."
+    ).unwrap();
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn full(rate: Rate, now: Instant) -> Self {
+        Bucket {
+            tokens: rate.burst() as f64,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, rate: Rate, now: Instant) {
+        let elapsed = now.duration_since(self.updated);
+        let elapsed = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9;
+        self.tokens = (self.tokens + elapsed * rate.rate()).min(rate.burst() as f64);
+        self.updated = now;
+    }
+}
+
+type Key = (IpAddr, Option<usize>);
+
+/// Buckets by client address and route, with their keys queued in the order
+/// the sweep gets to them.
+#[derive(Default)]
+struct Buckets {
+    map: HashMap<Key, Bucket>,
+    queue: VecDeque<Key>,
+}
+
+/// Limits the requests of each client address with token buckets, one for
+/// the whole API and one per limited route. A request has to take a token
+/// from both, and is rejected with `429 Too Many Requests` otherwise.
+///
+/// Routes are matched by pattern, with `:name` segments matching any
+/// segment, as in `/v1/wallets/:pub_key`.
+///
+/// Requests from a trusted proxy are counted against the client named in
+/// their `X-Forwarded-For` header, the last address there that isn't a
+/// trusted proxy itself. Other requests are counted against their peer
+/// address, whatever the header says.
+///
+/// Each request refills a few of the buckets, oldest first, and forgets the
+/// ones that are full, so that idle clients don't pile up.
+pub struct RateLimit {
+    per_ip: Option<Rate>,
+    routes: Vec<(Vec<String>, Rate)>,
+    trusted_proxies: Vec<IpAddr>,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimit {
+    /// Allow `per_ip` to every client over the whole API, if given, and the
+    /// rate of the first matching pattern of `routes` on each route.
+    pub fn new(
+        per_ip: Option<Rate>,
+        routes: Vec<(String, Rate)>,
+        trusted_proxies: Vec<IpAddr>,
+    ) -> Self {
+        let routes = routes
+            .into_iter()
+            .map(|(pattern, rate)| (segments(&pattern), rate))
+            .collect();
+        RateLimit {
+            per_ip,
+            routes,
+            trusted_proxies,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    fn client(&self, req: &Request) -> IpAddr {
+        let peer = req.remote_addr.ip();
+        if !self.trusted_proxies.contains(&peer) {
+            return peer;
+        }
+        let forwarded = match req.headers.get_raw("X-Forwarded-For") {
+            Some(lines) => lines,
+            None => return peer,
+        };
+        let hops = forwarded
+            .iter()
+            .filter_map(|line| str::from_utf8(line).ok())
+            .flat_map(|line| line.split(','))
+            .collect::<Vec<&str>>();
+
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            match hop.trim().parse() {
+                Ok(ip) => client = ip,
+                Err(_) => break,
+            }
+            if !self.trusted_proxies.contains(&client) {
+                break;
+            }
+        }
+        client
+    }
+
+    fn route(&self, path: &[&str]) -> Option<usize> {
+        let path = path.iter()
+            .cloned()
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<&str>>();
+        self.routes.iter().position(|&(ref pattern, _)| {
+            pattern.len() == path.len()
+                && pattern
+                    .iter()
+                    .zip(path.iter())
+                    .all(|(p, s)| p.starts_with(':') || p.as_str() == *s)
+        })
+    }
+
+    fn rate(&self, route: Option<usize>) -> Option<Rate> {
+        match route {
+            Some(i) => Some(self.routes[i].1),
+            None => self.per_ip,
+        }
+    }
+
+    /// Refill the `SWEEP_STEP` buckets checked longest ago and forget the
+    /// full ones, which are the same as missing ones.
+    fn sweep(&self, buckets: &mut Buckets, now: Instant) {
+        for _ in 0..SWEEP_STEP {
+            let key = match buckets.queue.pop_front() {
+                Some(key) => key,
+                None => return,
+            };
+            let full = match (buckets.map.get_mut(&key), self.rate(key.1)) {
+                (Some(bucket), Some(rate)) => {
+                    bucket.refill(rate, now);
+                    bucket.tokens >= rate.burst() as f64
+                }
+                _ => true,
+            };
+            if full {
+                buckets.map.remove(&key);
+            } else {
+                buckets.queue.push_back(key);
+            }
+        }
+    }
+
+    /// Take a token for `ip` on `route`, or return the seconds until one is
+    /// available.
+    fn take(&self, ip: IpAddr, route: Option<usize>, now: Instant) -> Result<(), u64> {
+        let mut keys = Vec::new();
+        if self.per_ip.is_some() {
+            keys.push((ip, None));
+        }
+        if route.is_some() {
+            keys.push((ip, route));
+        }
+
+        let mut buckets = self.buckets.lock().unwrap();
+        self.sweep(&mut buckets, now);
+
+        let mut wait = 0.0f64;
+        for key in &keys {
+            let rate = self.rate(key.1).unwrap();
+            if !buckets.map.contains_key(key) {
+                if buckets.map.len() >= MAX_BUCKETS {
+                    if let Some(oldest) = buckets.queue.pop_front() {
+                        buckets.map.remove(&oldest);
+                    }
+                }
+                buckets.map.insert(*key, Bucket::full(rate, now));
+                buckets.queue.push_back(*key);
+            }
+            let bucket = buckets.map.get_mut(key).unwrap();
+            bucket.refill(rate, now);
+            if bucket.tokens < 1.0 {
+                wait = wait.max((1.0 - bucket.tokens) / rate.rate());
+            }
+        }
+        if wait > 0.0 {
+            return Err(wait.ceil() as u64);
+        }
+
+        for key in &keys {
+            if let Some(bucket) = buckets.map.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn segments(pattern: &str) -> Vec<String> {
+    pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string())
+        .collect()
+}
+
+impl BeforeMiddleware for RateLimit {
+    fn before(&self, req: &mut Request) -> IronResult<()> {
+        let route = self.route(&req.url.path());
+        if self.per_ip.is_none() && route.is_none() {
+            return Ok(());
+        }
+
+        match self.take(self.client(req), route, Instant::now()) {
+            Ok(()) => Ok(()),
+            Err(retry_after) => {
+                RATE_LIMITED.inc();
+                let mut error = IronError::from(ApiError::TooManyRequests);
+                error
+                    .response
+                    .headers
+                    .set_raw("Retry-After", vec![retry_after.to_string().into_bytes()]);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Rejects requests whose body is larger than the limit with
+/// `413 Payload Too Large`, before it is read. Bodies sent without a length
+/// are cut at the limit when parsed.
+pub struct BodyLimit {
+    max: usize,
+    parser_limit: Read<bodyparser::MaxBodyLength>,
+}
+
+impl BodyLimit {
+    /// Accept bodies of up to `max` bytes.
+    pub fn new(max: usize) -> Self {
+        BodyLimit {
+            max,
+            parser_limit: Read::<bodyparser::MaxBodyLength>::one(max),
+        }
+    }
+}
+
+impl BeforeMiddleware for BodyLimit {
+    fn before(&self, req: &mut Request) -> IronResult<()> {
+        if let Some(&ContentLength(length)) = req.headers.get::<ContentLength>() {
+            if length > self.max as u64 {
+                BODY_TOO_LARGE.inc();
+                return Err(ApiError::RequestBodyTooLarge.into());
+            }
+        }
+        self.parser_limit.before(req)
+    }
+}
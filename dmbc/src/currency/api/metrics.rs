@@ -1,7 +1,6 @@
 use exonum::api::Api;
 use hyper::header::ContentType;
 use hyper::mime::Mime;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use prometheus;
@@ -22,7 +21,6 @@ impl Api for MetricsApi {
             let mut res = Response::with((status::Ok, String::from_utf8(buffer).unwrap()));
             res.headers
                 .set(ContentType(encoder.format_type().parse::<Mime>().unwrap()));
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             Ok(res)
         };
@@ -3,12 +3,18 @@
 
 pub mod asset;
 pub mod assets_intern;
+pub mod auth;
+pub mod backup;
 pub mod blocks;
 pub mod db_stats;
 pub mod error;
+pub mod events;
 pub mod fees;
+pub mod health;
 pub mod hex;
+pub mod limits;
 pub mod metrics;
+pub mod supply;
 pub mod transaction;
 pub mod wallet;
 pub mod offers;
@@ -17,25 +23,32 @@ pub mod history_offers;
 extern crate params;
 
 use exonum::api::Api;
-use exonum::blockchain::Blockchain;
-use exonum::node::ApiSender;
+use exonum::blockchain::{Blockchain, SharedNodeState};
+use exonum::crypto::PublicKey;
+use exonum::node::{ApiSender, TxPool};
+use exonum::storage::RocksDB;
 use hyper::header::{AccessControlAllowHeaders, AccessControlAllowMethods, Headers};
 use hyper::method::Method;
 use hyper::status::StatusCode;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use router::Router;
 use std::cmp;
+use std::path::PathBuf;
 use unicase::UniCase;
 
 use self::asset::AssetApi;
+use self::error::ApiError;
 use self::assets_intern::AssetInternApi;
+use self::backup::BackupApi;
 use self::blocks::BlocksApi;
 use self::db_stats::DbStatsApi;
+use self::events::EventsApi;
 use self::fees::FeesApi;
+use self::health::HealthApi;
 use self::hex::HexApi;
 use self::metrics::MetricsApi;
 use self::params::{FromValue, Params};
+use self::supply::SupplyApi;
 use self::transaction::TransactionApi;
 use self::wallet::WalletApi;
 use self::offers::OfferApi;
@@ -43,11 +56,22 @@ use self::history_offers::HistoryOffersApi;
 
 const PARAMETER_OFFSET_KEY: &str = "offset";
 const PARAMETER_LIMIT_KEY: &str = "limit";
+const PARAMETER_HEIGHT_KEY: &str = "height";
 
 #[derive(Clone)]
 pub struct ServiceApi {
     pub channel: ApiSender,
     pub blockchain: Blockchain,
+    pub tx_pool: Option<TxPool>,
+    pub node_state: Option<SharedNodeState>,
+    pub service_key: PublicKey,
+}
+
+/// Endpoints served on the private API address only.
+#[derive(Clone)]
+pub struct PrivateApi {
+    pub database: Option<RocksDB>,
+    pub backup_dir: PathBuf,
 }
 
 impl ServiceApi {
@@ -78,6 +102,21 @@ impl ServiceApi {
         elements
     }
 
+    /// returns the block height if `request` has the `height` parameter.
+    ///
+    /// # URL request
+    ///
+    /// ` https://blockchain.com/api/services/cryptocurrency/v1/wallets/<pub_key>?height=1024 `
+    pub fn height_param(req: &mut Request) -> Result<Option<u64>, ApiError> {
+        let parameters = req.get_ref::<Params>().unwrap();
+        match parameters.get(PARAMETER_HEIGHT_KEY) {
+            Some(value) => FromValue::from_value(value)
+                .map(Some)
+                .ok_or(ApiError::IncorrectRequest),
+            None => Ok(None),
+        }
+    }
+
     pub fn pagination_params(req: &mut Request) -> (u64, u64) {
         let parameters = req.get_ref::<Params>().unwrap();
         let offset_parameter = parameters.get(PARAMETER_OFFSET_KEY);
@@ -106,10 +145,11 @@ impl ServiceApi {
     }
 
     pub fn add_option_headers(headers: &mut Headers) {
-        headers.set(AccessControlAllowOrigin::Any);
-        headers.set(AccessControlAllowHeaders(vec![UniCase(
-            "content-type".to_owned(),
-        )]));
+        headers.set(AccessControlAllowHeaders(vec![
+            UniCase("content-type".to_owned()),
+            UniCase("authorization".to_owned()),
+            UniCase(auth::API_KEY_HEADER.to_lowercase()),
+        ]));
         headers.set(AccessControlAllowMethods(vec![
             Method::Get,
             Method::Post,
@@ -124,6 +164,7 @@ impl Api for ServiceApi {
         let api = TransactionApi {
             channel: self_.channel,
             blockchain: self_.blockchain,
+            tx_pool: self_.tx_pool,
         };
         api.wire(router);
 
@@ -140,15 +181,6 @@ impl Api for ServiceApi {
         let api = HexApi {};
         api.wire(router);
 
-        let api = AssetInternApi {};
-        api.wire(router);
-
-        let api = MetricsApi {};
-        api.wire(router);
-
-        let api = DbStatsApi {};
-        api.wire(router);
-
         let api = FeesApi {
             blockchain: self.clone().blockchain,
         };
@@ -169,6 +201,22 @@ impl Api for ServiceApi {
         };
         api.wire(router);
 
+        let api = SupplyApi {
+            blockchain: self.clone().blockchain,
+        };
+        api.wire(router);
+
+        let api = EventsApi {};
+        api.wire(router);
+
+        let api = HealthApi {
+            blockchain: self.clone().blockchain,
+            tx_pool: self.clone().tx_pool,
+            node_state: self.clone().node_state,
+            service_key: self.service_key,
+        };
+        api.wire(router);
+
         let send_option = move |_request: &mut Request| -> IronResult<Response> {
             let mut resp = Response::with(StatusCode::Ok);
             ServiceApi::add_option_headers(&mut resp.headers);
@@ -178,3 +226,24 @@ impl Api for ServiceApi {
         router.options("/*", send_option, "send_options");
     }
 }
+
+impl Api for PrivateApi {
+    fn wire(&self, router: &mut Router) {
+        let api = AssetInternApi {};
+        api.wire(router);
+
+        let api = MetricsApi {};
+        api.wire(router);
+
+        let api = DbStatsApi {};
+        api.wire(router);
+
+        if let Some(database) = self.database.clone() {
+            let api = BackupApi {
+                database,
+                backup_dir: self.backup_dir.clone(),
+            };
+            api.wire(router);
+        }
+    }
+}
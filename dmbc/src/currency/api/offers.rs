@@ -10,7 +10,6 @@ use currency::offers::OpenOffers;
 use exonum::api::Api;
 use exonum::blockchain::Blockchain;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use prometheus::IntCounter;
@@ -113,7 +112,6 @@ impl Api for OfferApi {
             let mut res =
                 Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             LIST_RESPONSES.inc();
 
@@ -145,7 +143,6 @@ impl Api for OfferApi {
             let body = serde_json::to_string_pretty(&result).unwrap();
             let mut res = Response::with((status_code, body));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             INFO_RESPONSES.inc();
             Ok(res)
@@ -0,0 +1,69 @@
+extern crate serde_json;
+
+use exonum::api::Api;
+use exonum::blockchain::Blockchain;
+use hyper::header::ContentType;
+use iron::prelude::*;
+use iron::status;
+use prometheus::IntCounter;
+use router::Router;
+
+use currency::api::error::ApiError;
+use currency::supply;
+use currency::supply::Supply;
+
+#[derive(Clone)]
+pub struct SupplyApi {
+    pub blockchain: Blockchain,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SupplyResponseBody {
+    pub supply: Supply,
+    pub conserved: bool,
+}
+
+pub type SupplyResponse = Result<SupplyResponseBody, ApiError>;
+
+impl SupplyApi {
+    fn supply(&self) -> Supply {
+        let view = self.blockchain.snapshot();
+        supply::Schema(view).audit()
+    }
+}
+
+lazy_static! {
+    static ref SUPPLY_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_supply_api_requests_total",
+        "Coin supply requests."
+    ).unwrap();
+    static ref SUPPLY_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_supply_api_responses_total",
+        "Coin supply responses."
+    ).unwrap();
+}
+
+impl Api for SupplyApi {
+    fn wire(&self, router: &mut Router) {
+        let self_ = self.clone();
+        let supply = move |_: &mut Request| -> IronResult<Response> {
+            SUPPLY_REQUESTS.inc();
+
+            let supply = self_.supply();
+            let result: SupplyResponse = Ok(SupplyResponseBody {
+                supply,
+                conserved: supply.is_conserved(),
+            });
+
+            let mut res =
+                Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
+            res.headers.set(ContentType::json());
+
+            SUPPLY_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        router.get("/v1/supply", supply, "supply");
+    }
+}
@@ -5,32 +5,42 @@ extern crate router;
 extern crate serde;
 extern crate serde_json;
 
+use std::panic;
+
 use exonum::api::Api;
-use exonum::blockchain::{Blockchain, Transaction};
-use exonum::crypto::Hash;
+use exonum::blockchain::{self, Blockchain, Transaction};
+use exonum::crypto::{Hash, PublicKey};
+use exonum::encoding::serialize::json::ExonumJson;
 use exonum::encoding::serialize::FromHex;
 use exonum::messages::Message;
-use exonum::node::{ApiSender, TransactionSend};
+use exonum::node::{ApiSender, TransactionSend, TxPool};
+use exonum::storage::{Fork, MapIndex, Patch, StorageKey, StorageValue};
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status as istatus;
 use prometheus::IntCounter;
 use router::Router;
 
 use currency::api::error::ApiError;
+use currency::assets::{AssetId, AssetInfo};
+use currency::offers::OpenOffers;
 use currency::status;
+use currency::transactions::components::metrics;
 use currency::transactions::{AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
                              TradeIntermediary, Transfer, TransferWithFeesPayer, BidOffer, AskOffer};
 
 use currency::error::Error;
+use currency::wallet::Wallet;
+use currency::SERVICE_NAME;
 
 const MAX_TRANSACTION_LENGTH: usize = 17408;
+const MAX_BATCH_LENGTH: usize = 1000;
 
 #[derive(Clone)]
 pub struct TransactionApi {
     pub channel: ApiSender,
     pub blockchain: Blockchain,
+    pub tx_pool: Option<TxPool>,
 }
 
 #[serde(untagged)]
@@ -89,14 +99,226 @@ pub struct TransactionResponse {
 
 pub type TxPostResponse = Result<Result<TransactionResponse, Error>, ApiError>;
 
+pub type TxBatchPostResponse = Result<Vec<Result<TransactionResponse, Error>>, ApiError>;
+
 //#[derive(Serialize, Deserialize, Debug)]
 pub type StatusResponse = Result<Result<(), Error>, ApiError>;
 
+/// Where a transaction is in its lifecycle.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionState {
+    Unknown,
+    InMempool,
+    Committed,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+pub struct TransactionLocation {
+    pub height: u64,
+    pub index: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TransactionInfo {
+    pub state: TransactionState,
+    /// Block height and position in the block of a committed transaction.
+    pub location: Option<TransactionLocation>,
+    /// Decoded transaction body.
+    pub content: Option<serde_json::Value>,
+    /// Execution result of a committed transaction.
+    pub status: Option<Result<(), Error>>,
+}
+
+impl TransactionInfo {
+    fn unknown() -> Self {
+        TransactionInfo {
+            state: TransactionState::Unknown,
+            location: None,
+            content: None,
+            status: None,
+        }
+    }
+}
+
+pub type TransactionInfoResponse = Result<TransactionInfo, ApiError>;
+
+/// State of a single index entry before and after a simulated transaction.
+/// `None` means the entry is absent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StateChange<K, V> {
+    pub key: K,
+    pub before: Option<V>,
+    pub after: Option<V>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SimulationResponseBody {
+    pub tx_hash: Hash,
+    pub status: Result<(), Error>,
+    pub wallets: Vec<StateChange<PublicKey, Wallet>>,
+    pub assets: Vec<StateChange<AssetId, AssetInfo>>,
+    pub offers: Vec<StateChange<AssetId, OpenOffers>>,
+}
+
+pub type SimulateResponse = Result<Result<SimulationResponseBody, Error>, ApiError>;
+
 impl TransactionApi {
-    fn get_status(&self, tx_hash: &Hash) -> Option<Result<(), Error>> {
-        let view = &mut self.blockchain.fork();
-        status::Schema(view).fetch(tx_hash)
+    fn post(&self, transaction: TransactionRequest) -> Result<TransactionResponse, Error> {
+        if transaction.len() > MAX_TRANSACTION_LENGTH {
+            return Err(Error::InvalidTransaction);
+        }
+        let tx: Box<Transaction> = transaction.into();
+        let tx_hash = tx.hash();
+        match self.channel.send(tx) {
+            Ok(_) => Ok(TransactionResponse { tx_hash }),
+            Err(_) => Err(Error::UnableToVerifyTransaction),
+        }
+    }
+
+    /// Items are parsed one by one so that a malformed transaction doesn't
+    /// reject the whole batch.
+    fn post_batch(&self, batch: Vec<serde_json::Value>) -> Vec<Result<TransactionResponse, Error>> {
+        batch
+            .into_iter()
+            .map(|item| {
+                POST_REQUESTS.inc();
+                let result = serde_json::from_value::<TransactionRequest>(item)
+                    .map_err(|_| Error::InvalidTransaction)
+                    .and_then(|transaction| self.post(transaction));
+                POST_RESPONSES.inc();
+                result
+            })
+            .collect()
     }
+
+    fn get_transaction(&self, tx_hash: &Hash) -> TransactionInfo {
+        let view = self.blockchain.snapshot();
+        let schema = blockchain::Schema::new(&view);
+
+        if let Some(location) = schema.tx_location_by_tx_hash().get(tx_hash) {
+            let content = schema
+                .transactions()
+                .get(tx_hash)
+                .and_then(|raw| self.blockchain.tx_from_raw(raw))
+                .and_then(|tx| tx.serialize_field().ok());
+            return TransactionInfo {
+                state: TransactionState::Committed,
+                location: Some(TransactionLocation {
+                    height: location.block_height().0,
+                    index: location.position_in_block(),
+                }),
+                content,
+                status: status::Schema(&view).fetch(tx_hash),
+            };
+        }
+
+        let pending = self.tx_pool.as_ref().and_then(|pool| {
+            pool.read()
+                .expect("Expected read lock")
+                .get(tx_hash)
+                .and_then(|tx| tx.serialize_field().ok())
+        });
+        match pending {
+            Some(content) => TransactionInfo {
+                state: TransactionState::InMempool,
+                location: None,
+                content: Some(content),
+                status: None,
+            },
+            None => TransactionInfo::unknown(),
+        }
+    }
+
+    /// Execute the transaction on a fork of the current state and report
+    /// what it would change. Nothing is broadcast or committed.
+    ///
+    /// A transaction that panics changes nothing, as when it is committed,
+    /// and is reported as failed.
+    fn simulate(&self, tx: Box<Transaction>) -> SimulationResponseBody {
+        let tx_hash = tx.hash();
+        let mut fork = self.blockchain.fork();
+
+        // Everything executed after the checkpoint is rolled back below, so
+        // the `before` values are read from the very same snapshot.
+        fork.checkpoint();
+        let executed = panic::catch_unwind(panic::AssertUnwindSafe(|| tx.execute(&mut fork)));
+        if let Err(err) = executed {
+            fork.rollback();
+            error!("Simulation of transaction {:?} panicked: {:?}", tx_hash, err);
+            return SimulationResponseBody {
+                tx_hash,
+                status: Err(Error::UnableToVerifyTransaction),
+                wallets: Vec::new(),
+                assets: Vec::new(),
+                offers: Vec::new(),
+            };
+        }
+
+        let status = status::Schema(&fork)
+            .fetch(&tx_hash)
+            .unwrap_or(Err(Error::UnableToVerifyTransaction));
+        let wallets = changed_entries::<PublicKey, Wallet>(&fork, ".wallets");
+        let assets = changed_entries::<AssetId, AssetInfo>(&fork, ".assets");
+        let offers = changed_entries::<AssetId, OpenOffers>(&fork, ".open_offers");
+
+        fork.rollback();
+
+        SimulationResponseBody {
+            tx_hash,
+            status,
+            wallets: with_previous_values(&fork, ".wallets", wallets),
+            assets: with_previous_values(&fork, ".assets", assets),
+            offers: with_previous_values(&fork, ".open_offers", offers),
+        }
+    }
+}
+
+/// Collect the entries of the service index `suffix` touched by the fork
+/// together with their new values.
+fn changed_entries<K, V>(fork: &Fork, suffix: &str) -> Vec<(K, Option<V>)>
+where
+    K: StorageKey,
+    V: StorageValue,
+{
+    let name = SERVICE_NAME.to_string() + suffix;
+    changed_keys(fork.patch(), &name)
+        .into_iter()
+        .map(|key| {
+            let value = MapIndex::<&Fork, K, V>::new(name.clone(), fork).get(&key);
+            (key, value)
+        })
+        .collect()
+}
+
+fn changed_keys<K: StorageKey>(patch: &Patch, name: &str) -> Vec<K> {
+    patch
+        .iter()
+        .filter(|&(index, _)| index == name)
+        .flat_map(|(_, changes)| changes.iter())
+        .map(|(key, _)| K::read(key))
+        .collect()
+}
+
+fn with_previous_values<K, V>(
+    fork: &Fork,
+    suffix: &str,
+    entries: Vec<(K, Option<V>)>,
+) -> Vec<StateChange<K, V>>
+where
+    K: StorageKey,
+    V: StorageValue + PartialEq,
+{
+    let index: MapIndex<&Fork, K, V> = MapIndex::new(SERVICE_NAME.to_string() + suffix, fork);
+    entries
+        .into_iter()
+        .map(|(key, after)| StateChange {
+            before: index.get(&key),
+            key,
+            after,
+        })
+        .filter(|change| change.before != change.after)
+        .collect()
 }
 
 lazy_static! {
@@ -108,6 +330,22 @@ lazy_static! {
         "dmbc_transaction_api_post_responses_total",
         "Transaction post responses."
     ).unwrap();
+    static ref BATCH_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_batch_requests_total",
+        "Transaction batch post requests."
+    ).unwrap();
+    static ref BATCH_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_batch_responses_total",
+        "Transaction batch post responses."
+    ).unwrap();
+    static ref SIMULATE_REQUESTS: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_simulate_requests_total",
+        "Transaction simulation requests."
+    ).unwrap();
+    static ref SIMULATE_RESPONSES: IntCounter = register_int_counter!(
+        "dmbc_transaction_api_simulate_responses_total",
+        "Transaction simulation responses."
+    ).unwrap();
     static ref GET_STATUS_REQUESTS: IntCounter = register_int_counter!(
         "dmbc_transaction_api_get_status_requests_total",
         "Transaction status requests."
@@ -125,17 +363,7 @@ impl Api for TransactionApi {
             POST_REQUESTS.inc();
 
             let s: TxPostResponse = match req.get::<bodyparser::Struct<TransactionRequest>>() {
-                Ok(Some(ref transaction)) if transaction.len() > MAX_TRANSACTION_LENGTH => {
-                    Ok(Err(Error::InvalidTransaction))
-                }
-                Ok(Some(transaction)) => {
-                    let tx: Box<Transaction> = transaction.into();
-                    let tx_hash = tx.hash();
-                    match self_.channel.send(tx) {
-                        Ok(_) => Ok(Ok(TransactionResponse { tx_hash })),
-                        Err(_) => Ok(Err(Error::UnableToVerifyTransaction)),
-                    }
-                }
+                Ok(Some(transaction)) => Ok(self_.post(transaction)),
                 Ok(None) => Err(ApiError::EmptyRequestBody),
                 Err(_) => Err(ApiError::IncorrectRequest),
             };
@@ -151,7 +379,6 @@ impl Api for TransactionApi {
 
             let mut res = Response::with((ss, serde_json::to_string_pretty(&s).unwrap()));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             POST_RESPONSES.inc();
 
@@ -160,18 +387,18 @@ impl Api for TransactionApi {
         // Bind the transaction handler to a specific route.
 
         let self_ = self.clone();
-        let get_status = move |request: &mut Request| -> IronResult<Response> {
-            GET_STATUS_REQUESTS.inc();
+        let batch = move |req: &mut Request| -> IronResult<Response> {
+            BATCH_REQUESTS.inc();
 
-            let path = request.url.path();
-            let tx_hash_str = path.last().unwrap();
-            let s: StatusResponse = Hash::from_hex(tx_hash_str)
-                .map_err(|_| ApiError::TransactionHashInvalid)
-                .and_then(|tx_hash| {
-                    self_
-                        .get_status(&tx_hash)
-                        .ok_or(ApiError::TransactionNotFound)
-                });
+            let s: TxBatchPostResponse =
+                match req.get::<bodyparser::Struct<Vec<serde_json::Value>>>() {
+                    Ok(Some(ref batch)) if batch.len() > MAX_BATCH_LENGTH => {
+                        Err(ApiError::BatchTooLarge)
+                    }
+                    Ok(Some(batch)) => Ok(self_.post_batch(batch)),
+                    Ok(None) => Err(ApiError::EmptyRequestBody),
+                    Err(_) => Err(ApiError::IncorrectRequest),
+                };
 
             let mut res = Response::with((
                 s.clone()
@@ -181,7 +408,65 @@ impl Api for TransactionApi {
                 serde_json::to_string_pretty(&s).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
+
+            BATCH_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        let self_ = self.clone();
+        let simulate = move |req: &mut Request| -> IronResult<Response> {
+            SIMULATE_REQUESTS.inc();
+
+            let s: SimulateResponse = match req.get::<bodyparser::Struct<TransactionRequest>>() {
+                Ok(Some(ref transaction)) if transaction.len() > MAX_TRANSACTION_LENGTH => {
+                    Ok(Err(Error::InvalidTransaction))
+                }
+                Ok(Some(transaction)) => {
+                    let tx: Box<Transaction> = transaction.into();
+                    metrics::dry_run(|| {
+                        if tx.verify() {
+                            Ok(Ok(self_.simulate(tx)))
+                        } else {
+                            Ok(Err(Error::InvalidTransaction))
+                        }
+                    })
+                }
+                Ok(None) => Err(ApiError::EmptyRequestBody),
+                Err(_) => Err(ApiError::IncorrectRequest),
+            };
+            let ss = s
+                .clone()
+                .ok()
+                .map(|r| r.err().map(|_| istatus::BadRequest).unwrap_or(istatus::Ok))
+                .unwrap_or(istatus::BadRequest);
+
+            let mut res = Response::with((ss, serde_json::to_string_pretty(&s).unwrap()));
+            res.headers.set(ContentType::json());
+
+            SIMULATE_RESPONSES.inc();
+
+            Ok(res)
+        };
+
+        let self_ = self.clone();
+        let get_status = move |request: &mut Request| -> IronResult<Response> {
+            GET_STATUS_REQUESTS.inc();
+
+            let path = request.url.path();
+            let tx_hash_str = path.last().unwrap();
+            let s: TransactionInfoResponse = Hash::from_hex(tx_hash_str)
+                .map_err(|_| ApiError::TransactionHashInvalid)
+                .map(|tx_hash| self_.get_transaction(&tx_hash));
+
+            let ss = match s {
+                Ok(ref info) if info.state == TransactionState::Unknown => istatus::NotFound,
+                Ok(_) => istatus::Ok,
+                Err(ref e) => e.to_status(),
+            };
+
+            let mut res = Response::with((ss, serde_json::to_string_pretty(&s).unwrap()));
+            res.headers.set(ContentType::json());
 
             GET_STATUS_RESPONSES.inc();
 
@@ -189,6 +474,12 @@ impl Api for TransactionApi {
         };
 
         router.post("/v1/transactions", transaction, "transaction");
+        router.post("/v1/transactions/batch", batch, "transaction_batch");
+        router.post(
+            "/v1/transactions/simulate",
+            simulate,
+            "simulate_transaction",
+        );
         router.get(
             "/v1/transactions/:hash",
             get_status,
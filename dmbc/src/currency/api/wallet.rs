@@ -3,11 +3,10 @@ extern crate serde_json;
 use std::collections::HashMap;
 
 use exonum::api::Api;
-use exonum::blockchain::Blockchain;
+use exonum::blockchain::{self, Blockchain};
 use exonum::crypto::PublicKey;
 use exonum::encoding::serialize::FromHex;
 use hyper::header::ContentType;
-use iron::headers::AccessControlAllowOrigin;
 use iron::prelude::*;
 use iron::status;
 use prometheus::IntCounter;
@@ -18,6 +17,7 @@ use currency::api::ServiceApi;
 use currency::assets;
 use currency::assets::{AssetBundle, AssetId, AssetInfo};
 use currency::wallet;
+use currency::wallet::history;
 use currency::wallet::Wallet;
 
 pub const PARAMETER_META_DATA_KEY: &str = "meta_data";
@@ -90,6 +90,21 @@ impl WalletApi {
         wallet::Schema(view).fetch(pub_key)
     }
 
+    /// The wallet at the end of the block at `height`, or the latest one.
+    fn wallet_at(&self, pub_key: &PublicKey, height: Option<u64>) -> Result<Wallet, ApiError> {
+        let height = match height {
+            Some(height) => height,
+            None => return Ok(self.wallet(pub_key)),
+        };
+        let view = self.blockchain.snapshot();
+        if height >= blockchain::Schema::new(&view).block_hashes_by_height().len() {
+            return Err(ApiError::BlockNotFound);
+        }
+        history::Schema(&view)
+            .fetch(pub_key, height)
+            .ok_or(ApiError::HistoryUnavailable)
+    }
+
     fn wallets(&self) -> HashMap<PublicKey, WalletInfo> {
         let view = &mut self.blockchain.fork();
         let index = wallet::Schema(view).index();
@@ -130,10 +145,10 @@ impl WalletApi {
         wallets
     }
 
-    fn assets(&self, pub_key: &PublicKey, creators: Vec<PublicKey>) -> Vec<AssetBundle> {
+    fn assets(&self, wallet: &Wallet, creators: Vec<PublicKey>) -> Vec<AssetBundle> {
         if creators.len() > 0 {
             let mut assets: Vec<AssetBundle> = vec![];
-            for asset in self.wallet(pub_key).assets() {
+            for asset in wallet.assets() {
                 match self.asset_info(&asset.id()) {
                     Some(info) => {
                         if creators.iter().find(|& x| x == info.creator()).is_some() {
@@ -147,7 +162,7 @@ impl WalletApi {
             return assets;
         }
 
-        self.wallet(pub_key).assets()
+        wallet.assets()
     }
 
     fn asset_info(&self, asset_id: &AssetId) -> Option<AssetInfo> {
@@ -198,13 +213,13 @@ impl Api for WalletApi {
         let wallet_info = move |req: &mut Request| -> IronResult<Response> {
             BALANCE_REQUESTS.inc();
 
+            let height = ServiceApi::height_param(req);
             let path = req.url.path();
             let wallet_key = path.last().unwrap();
             let result: WalletResponse = match PublicKey::from_hex(wallet_key) {
-                Ok(public_key) => {
-                    let wallet = self_.wallet(&public_key);
-                    Ok(WalletInfo::from(wallet))
-                }
+                Ok(public_key) => height
+                    .and_then(|height| self_.wallet_at(&public_key, height))
+                    .map(WalletInfo::from),
                 Err(_) => Err(ApiError::WalletHexInvalid),
             };
 
@@ -217,7 +232,6 @@ impl Api for WalletApi {
                 serde_json::to_string_pretty(&result).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             BALANCE_RESPONSES.inc();
 
@@ -239,7 +253,6 @@ impl Api for WalletApi {
             let mut res =
                 Response::with((status::Ok, serde_json::to_string_pretty(&result).unwrap()));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             LIST_RESPONSES.inc();
 
@@ -271,7 +284,6 @@ impl Api for WalletApi {
                         serde_json::to_string_pretty(&result).unwrap(),
                     ));
                     res.headers.set(ContentType::json());
-                    res.headers.set(AccessControlAllowOrigin::Any);
 
                     ASSETS_RESPONSES.inc();
 
@@ -286,7 +298,6 @@ impl Api for WalletApi {
                             serde_json::to_string_pretty(&result).unwrap(),
                         ));
                         res.headers.set(ContentType::json());
-                        res.headers.set(AccessControlAllowOrigin::Any);
 
                         ASSETS_RESPONSES.inc();
 
@@ -297,10 +308,14 @@ impl Api for WalletApi {
                 }
             }
 
-            let result: WalletAssetsResponse = match public_key_result {
-                Ok(public_key) => {
-                    
-                    let assets = self_.assets(&public_key, creators);
+            let height = ServiceApi::height_param(req);
+            let wallet = match public_key_result {
+                Ok(public_key) => height.and_then(|height| self_.wallet_at(&public_key, height)),
+                Err(_) => Err(ApiError::WalletHexInvalid),
+            };
+            let result: WalletAssetsResponse = match wallet {
+                Ok(wallet) => {
+                    let assets = self_.assets(&wallet, creators);
                     let extend_assets =
                         ServiceApi::read_parameter(req, PARAMETER_META_DATA_KEY, false);
                     // apply pagination parameters if they exist
@@ -324,7 +339,7 @@ impl Api for WalletApi {
                         assets: assets_list,
                     })
                 }
-                Err(_) => Err(ApiError::WalletHexInvalid),
+                Err(e) => Err(e),
             };
 
             let mut res = Response::with((
@@ -336,7 +351,6 @@ impl Api for WalletApi {
                 serde_json::to_string_pretty(&result).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             ASSETS_RESPONSES.inc();
 
@@ -367,7 +381,7 @@ impl Api for WalletApi {
             let result: WalletAssetResponse = match public_key_result {
                 Ok(public_key) => match asset_id_result {
                     Ok(id) => {
-                        let assets = self_.assets(&public_key, vec![]);
+                        let assets = self_.assets(&self_.wallet(&public_key), vec![]);
                         let info =
                             if ServiceApi::read_parameter(req, PARAMETER_META_DATA_KEY, false) {
                                 self_.asset_info(&id)
@@ -393,7 +407,6 @@ impl Api for WalletApi {
                 serde_json::to_string_pretty(&result).unwrap(),
             ));
             res.headers.set(ContentType::json());
-            res.headers.set(AccessControlAllowOrigin::Any);
 
             ASSET_RESPONSES.inc();
             Ok(res)
@@ -12,6 +12,6 @@ pub use currency::assets::asset_bundle::AssetBundle;
 pub use currency::assets::asset_id::AssetId;
 pub use currency::assets::asset_info::AssetInfo;
 pub use currency::assets::fees::{Fee, Fees};
-pub use currency::assets::meta_asset::MetaAsset;
+pub use currency::assets::meta_asset::{MetaAsset, ASSET_DATA_MAX_LENGTH};
 pub use currency::assets::schema::Schema;
 pub use currency::assets::trade_asset::TradeAsset;
@@ -0,0 +1,137 @@
+//! Consistency audit of asset accounting.
+
+use std::collections::{BTreeMap, HashSet};
+
+use exonum::crypto::PublicKey;
+use exonum::storage::Snapshot;
+
+use currency::assets;
+use currency::assets::AssetId;
+use currency::offers;
+use currency::wallet;
+
+/// An asset whose recorded amount differs from the amount actually held.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AssetMismatch {
+    /// Asset identifier.
+    pub id: AssetId,
+    /// Amount recorded in `AssetInfo`.
+    pub info_amount: u64,
+    /// Amount held by wallets.
+    pub wallets_amount: u64,
+    /// Amount locked in open bids.
+    pub escrowed_amount: u64,
+}
+
+/// Result of an asset conservation audit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
+pub struct AssetAudit {
+    /// Number of wallets walked.
+    pub wallets: u64,
+    /// Number of asset infos walked.
+    pub assets: u64,
+    /// Assets whose `AssetInfo::amount` differs from the holdings.
+    pub mismatched: Vec<AssetMismatch>,
+    /// Wallets stored with neither coins nor assets.
+    pub orphaned_wallets: Vec<PublicKey>,
+    /// Assets held in wallets or offers that have no `AssetInfo`.
+    pub assets_without_info: Vec<AssetId>,
+    /// Assets whose holdings add up to more than fits in `u64`.
+    pub overflowed: Vec<AssetId>,
+}
+
+impl AssetAudit {
+    /// Checks whether the audit found no problems.
+    pub fn is_clean(&self) -> bool {
+        self.mismatched.is_empty()
+            && self.orphaned_wallets.is_empty()
+            && self.assets_without_info.is_empty()
+            && self.overflowed.is_empty()
+    }
+}
+
+/// Amounts of an asset held, `None` once they overflow.
+struct Holdings {
+    wallets: Option<u64>,
+    escrowed: Option<u64>,
+}
+
+impl Default for Holdings {
+    fn default() -> Self {
+        Holdings {
+            wallets: Some(0),
+            escrowed: Some(0),
+        }
+    }
+}
+
+impl Holdings {
+    /// Everything held, or `None` if it doesn't fit in `u64`.
+    fn total(&self) -> Option<u64> {
+        self.wallets?.checked_add(self.escrowed?)
+    }
+}
+
+/// Walk wallets, asset infos and open offers and report every inconsistency
+/// between them.
+pub fn audit<S: AsRef<Snapshot>>(view: S) -> AssetAudit {
+    let view = view.as_ref();
+    let mut result = AssetAudit::default();
+    let mut holdings: BTreeMap<String, (AssetId, Holdings)> = BTreeMap::new();
+
+    for (pub_key, wallet) in wallet::Schema(view).index().iter() {
+        result.wallets += 1;
+        if wallet.balance() == 0 && wallet.assets().is_empty() {
+            result.orphaned_wallets.push(pub_key);
+        }
+        for bundle in wallet.assets() {
+            let entry = holdings
+                .entry(bundle.id().to_string())
+                .or_insert_with(|| (bundle.id(), Holdings::default()));
+            entry.1.wallets = entry.1.wallets.and_then(|w| w.checked_add(bundle.amount()));
+        }
+    }
+
+    for (id, open_offers) in offers::Schema(view).index().iter() {
+        let entry = holdings
+            .entry(id.to_string())
+            .or_insert_with(|| (id, Holdings::default()));
+        for bids in open_offers.bids() {
+            for bid in bids.offers() {
+                entry.1.escrowed = entry.1.escrowed.and_then(|e| e.checked_add(bid.amount()));
+            }
+        }
+    }
+
+    let mut with_info = HashSet::new();
+    for (id, info) in assets::Schema(view).index().iter() {
+        result.assets += 1;
+        with_info.insert(id);
+
+        let (wallets_amount, escrowed_amount, total) = holdings
+            .get(&id.to_string())
+            .map(|&(_, ref h)| (h.wallets, h.escrowed, h.total()))
+            .unwrap_or((Some(0), Some(0), Some(0)));
+
+        // Overflowed holdings are reported below rather than as a mismatch.
+        if total.map_or(false, |total| total != info.amount()) {
+            result.mismatched.push(AssetMismatch {
+                id,
+                info_amount: info.amount(),
+                wallets_amount: wallets_amount.unwrap_or(0),
+                escrowed_amount: escrowed_amount.unwrap_or(0),
+            });
+        }
+    }
+
+    for (_, (id, h)) in holdings {
+        if !with_info.contains(&id) && h.total() != Some(0) {
+            result.assets_without_info.push(id);
+        }
+        if h.total().is_none() {
+            result.overflowed.push(id);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,51 @@
+//! Hot backups of the node database.
+//!
+//! A backup is a RocksDB checkpoint: a complete database that a node can be
+//! started from. It is taken from a snapshot, so the node keeps running and
+//! committing blocks while it is written.
+
+use std::path::Path;
+
+use exonum::blockchain;
+use exonum::crypto::Hash;
+use exonum::storage::{self, Database, RocksDB, RocksDBOptions};
+
+/// Summary of a checkpoint.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    /// Where the checkpoint is stored.
+    pub path: String,
+    /// Height of the last block in the checkpoint.
+    pub height: u64,
+    /// Hash of the last block in the checkpoint.
+    pub block_hash: Hash,
+}
+
+/// Write a checkpoint of `db` to `path`, which must not exist, and verify it.
+pub fn create<P: AsRef<Path>>(db: &RocksDB, path: P) -> storage::Result<Checkpoint> {
+    db.checkpoint(&path)?;
+    verify(path)
+}
+
+/// Open the checkpoint at `path` and check that it holds a blockchain. The
+/// checkpoint is opened read-only, so verifying leaves it untouched.
+pub fn verify<P: AsRef<Path>>(path: P) -> storage::Result<Checkpoint> {
+    let path = path.as_ref();
+    let options = RocksDBOptions::default();
+    let db = RocksDB::open_read_only(path, &options)?;
+
+    let snapshot = db.snapshot();
+    let schema = blockchain::Schema::new(snapshot.as_ref());
+    if schema.block_hashes_by_height().len() == 0 {
+        return Err(storage::Error::new(format!(
+            "{} contains no blocks",
+            path.display()
+        )));
+    }
+    let last_block = schema.last_block();
+    Ok(Checkpoint {
+        path: path.display().to_string(),
+        height: last_block.height().0,
+        block_hash: last_block.hash(),
+    })
+}
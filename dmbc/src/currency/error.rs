@@ -49,6 +49,20 @@ impl Error {
             _ => None,
         }
     }
+
+    /// The `snake_case` name of the error, as used in logs and metrics.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Error::AssetNotFound => "asset_not_found",
+            Error::TransactionNotFound => "transaction_not_found",
+            Error::InvalidAssetInfo => "invalid_asset_info",
+            Error::InsufficientFunds => "insufficient_funds",
+            Error::InsufficientAssets => "insufficient_assets",
+            Error::InvalidTransaction => "invalid_transaction",
+            Error::UnableToVerifyTransaction => "unable_to_verify_transaction",
+            Error::NotImplemented => "not_implemented",
+        }
+    }
 }
 
 impl error::Error for Error {
@@ -0,0 +1,504 @@
+//! Notifications about committed blocks and transactions.
+//!
+//! Transactions record their effects on wallets while executing; after the
+//! commit they are turned into `v1` events, published to the sinks and pushed
+//! to local subscribers.
+
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Mutex;
+
+use exonum::blockchain;
+use exonum::crypto::{Hash, PublicKey};
+use exonum::messages::{Message, RawTransaction};
+use exonum::storage::{Entry, Fork, MapIndex, Snapshot};
+use num_cpus;
+use serde_json;
+
+use currency::assets::AssetId;
+use currency::error::Error;
+use currency::status;
+use currency::transactions::components::{
+    metrics, sort_fee_items, FeeItem, FeeReason, FeesCalculator,
+};
+use currency::transactions::{
+    AddAssets, AskOffer, BidOffer, DeleteAssets, Exchange, ExchangeIntermediary, Trade,
+    TradeIntermediary, Transfer, TransferWithFeesPayer, ADD_ASSETS_ID, ASK_OFFER_ID,
+    BID_OFFER_ID, DELETE_ASSETS_ID, EXCHANGE_ID, EXCHANGE_INTERMEDIARY_ID, TRADE_ID,
+    TRADE_INTERMEDIARY_ID, TRANSFER_FEES_PAYER_ID, TRANSFER_ID,
+};
+use currency::wallet;
+use currency::wallet::Wallet;
+use currency::SERVICE_NAME;
+use logging;
+
+pub mod v1;
+
+pub use currency::events::v1::{
+    AssetChange, BlockCommitted, TransactionCommitted, TxResult, TxType, WalletChange,
+};
+
+/// Requests the public API serves at once, as Iron starts 8 workers per CPU.
+fn api_workers() -> usize {
+    8 * num_cpus::get()
+}
+
+/// Maximum number of simultaneously connected subscribers. Each of them
+/// holds an API worker while connected, so they may take a quarter of the
+/// workers at most and leave the rest to the other requests.
+pub fn max_subscribers() -> usize {
+    cmp::max(1, api_workers() / 4)
+}
+
+/// Events buffered for a subscriber before it is considered too slow and
+/// disconnected.
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+/// Wallets and assets touched by a transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct Participants {
+    pub wallets: Vec<PublicKey>,
+    pub assets: Vec<AssetId>,
+}
+
+impl Participants {
+    fn add_wallet(&mut self, pub_key: &PublicKey) {
+        if !self.wallets.contains(pub_key) {
+            self.wallets.push(*pub_key);
+        }
+    }
+
+    fn add_asset(&mut self, id: AssetId) {
+        if !self.assets.contains(&id) {
+            self.assets.push(id);
+        }
+    }
+
+    /// Decode a raw transaction and collect its participants.
+    pub fn from_raw(raw: RawTransaction) -> Option<Participants> {
+        let mut p = Participants::default();
+        match raw.message_type() {
+            TRANSFER_ID => {
+                let tx = Transfer::from_raw(raw).ok()?;
+                p.add_wallet(tx.from());
+                p.add_wallet(tx.to());
+                tx.assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            TRANSFER_FEES_PAYER_ID => {
+                let offer = TransferWithFeesPayer::from_raw(raw).ok()?.offer();
+                p.add_wallet(offer.from());
+                p.add_wallet(offer.to());
+                p.add_wallet(offer.fees_payer());
+                offer.assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            ADD_ASSETS_ID => {
+                let tx = AddAssets::from_raw(raw).ok()?;
+                p.add_wallet(tx.pub_key());
+                for meta in tx.meta_assets() {
+                    p.add_wallet(meta.receiver());
+                    p.add_asset(AssetId::from_data(meta.data(), tx.pub_key()));
+                }
+            }
+            DELETE_ASSETS_ID => {
+                let tx = DeleteAssets::from_raw(raw).ok()?;
+                p.add_wallet(tx.pub_key());
+                tx.assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            TRADE_ID => {
+                let offer = Trade::from_raw(raw).ok()?.offer();
+                p.add_wallet(offer.buyer());
+                p.add_wallet(offer.seller());
+                offer.assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            TRADE_INTERMEDIARY_ID => {
+                let offer = TradeIntermediary::from_raw(raw).ok()?.offer();
+                p.add_wallet(offer.buyer());
+                p.add_wallet(offer.seller());
+                p.add_wallet(offer.intermediary().wallet());
+                offer.assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            EXCHANGE_ID => {
+                let offer = Exchange::from_raw(raw).ok()?.offer();
+                p.add_wallet(offer.sender());
+                p.add_wallet(offer.recipient());
+                offer.sender_assets().iter().for_each(|a| p.add_asset(a.id()));
+                offer.recipient_assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            EXCHANGE_INTERMEDIARY_ID => {
+                let offer = ExchangeIntermediary::from_raw(raw).ok()?.offer();
+                p.add_wallet(offer.sender());
+                p.add_wallet(offer.recipient());
+                p.add_wallet(offer.intermediary().wallet());
+                offer.sender_assets().iter().for_each(|a| p.add_asset(a.id()));
+                offer.recipient_assets().iter().for_each(|a| p.add_asset(a.id()));
+            }
+            BID_OFFER_ID => {
+                let tx = BidOffer::from_raw(raw).ok()?;
+                p.add_wallet(tx.pub_key());
+                p.add_asset(tx.asset().id());
+            }
+            ASK_OFFER_ID => {
+                let tx = AskOffer::from_raw(raw).ok()?;
+                p.add_wallet(tx.pub_key());
+                p.add_asset(tx.asset().id());
+            }
+            _ => return None,
+        }
+        Some(p)
+    }
+}
+
+/// Effects of a transaction on wallets, recorded while it executes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct TxEffects {
+    pub changes: Vec<WalletChange>,
+    pub fees: Vec<FeeItem>,
+}
+
+/// Schema for accessing recorded transaction effects.
+///
+/// Effects are only needed to publish the events of their block, so they are
+/// kept until the first transaction of a later block is executed.
+#[derive(Clone, Debug)]
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    fn index(self) -> MapIndex<S, Hash, String> {
+        let key = SERVICE_NAME.to_string() + ".tx_effects";
+        MapIndex::new(key, self.0)
+    }
+
+    /// Fetch the effects of the transaction, if they are still kept.
+    pub fn fetch(self, tx_hash: &Hash) -> Option<TxEffects> {
+        let repr = self.index().get(tx_hash)?;
+        match serde_json::from_str(&repr) {
+            Ok(effects) => Some(effects),
+            Err(e) => {
+                error!("Invalid effects of transaction {:?}: {}", tx_hash, e);
+                None
+            }
+        }
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    fn index_mut(&mut self) -> MapIndex<&mut Fork, Hash, String> {
+        let key = SERVICE_NAME.to_string() + ".tx_effects";
+        MapIndex::new(key, &mut *self.0)
+    }
+
+    fn height_mut(&mut self) -> Entry<&mut Fork, u64> {
+        let key = SERVICE_NAME.to_string() + ".tx_effects_height";
+        Entry::new(key, &mut *self.0)
+    }
+
+    /// Store the effects of the transaction executed at `height`, dropping
+    /// the ones of earlier blocks.
+    pub fn store(&mut self, height: u64, tx_hash: Hash, effects: &TxEffects) {
+        if self.height_mut().get() != Some(height) {
+            self.index_mut().clear();
+            self.height_mut().set(height);
+        }
+        let repr = serde_json::to_string(effects).unwrap();
+        self.index_mut().put(&tx_hash, repr);
+    }
+}
+
+fn wallet_change(pub_key: &PublicKey, before: &Wallet, after: &Wallet) -> Option<WalletChange> {
+    let mut amounts: BTreeMap<String, (AssetId, i64)> = BTreeMap::new();
+    for bundle in before.assets() {
+        let entry = amounts
+            .entry(bundle.id().to_string())
+            .or_insert((bundle.id(), 0));
+        entry.1 -= bundle.amount() as i64;
+    }
+    for bundle in after.assets() {
+        let entry = amounts
+            .entry(bundle.id().to_string())
+            .or_insert((bundle.id(), 0));
+        entry.1 += bundle.amount() as i64;
+    }
+
+    let coins = after.balance() as i64 - before.balance() as i64;
+    let assets: Vec<AssetChange> = amounts
+        .into_iter()
+        .filter(|&(_, (_, amount))| amount != 0)
+        .map(|(_, (id, amount))| AssetChange { id, amount })
+        .collect();
+
+    if coins == 0 && assets.is_empty() {
+        return None;
+    }
+    Some(WalletChange {
+        wallet: *pub_key,
+        coins,
+        assets,
+    })
+}
+
+/// What the transaction being captured does, recorded as it happens.
+#[derive(Default)]
+struct Recording {
+    /// Stored wallets with their state before the first store.
+    wallets: Vec<(PublicKey, Wallet)>,
+    /// Fees that depend on the execution, as charged.
+    fees: Vec<FeeItem>,
+}
+
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = RefCell::new(None);
+}
+
+/// Records the transaction being captured until finished or dropped, so
+/// that a panicking transaction doesn't leave the recording running.
+struct Recorder;
+
+impl Recorder {
+    fn start() -> Self {
+        RECORDING.with(|recording| *recording.borrow_mut() = Some(Recording::default()));
+        Recorder
+    }
+
+    fn finish(self) -> Recording {
+        RECORDING
+            .with(|recording| recording.borrow_mut().take())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        RECORDING.with(|recording| recording.borrow_mut().take());
+    }
+}
+
+fn record<F: FnOnce(&mut Recording)>(f: F) {
+    RECORDING.with(|recording| {
+        if let Some(ref mut recording) = *recording.borrow_mut() {
+            f(recording);
+        }
+    });
+}
+
+/// Note that a wallet is being stored over `previous`, if a transaction is
+/// being captured.
+pub fn wallet_stored(pub_key: &PublicKey, previous: &Wallet) {
+    record(|recording| {
+        if !recording.wallets.iter().any(|&(ref key, _)| key == pub_key) {
+            recording.wallets.push((*pub_key, previous.clone()));
+        }
+    });
+}
+
+/// Note a fee charged by the transaction being captured, if any. Fees of
+/// offers depend on the matched counter offers, so they are noted here.
+pub fn fee_charged(item: FeeItem) {
+    record(|recording| recording.fees.push(item));
+}
+
+/// Blockchain fees are charged first and kept when a transaction fails
+/// afterwards; nothing else is. The ones charged are the leading blockchain
+/// fees their recipients received.
+fn charged_before_failure(fees: Vec<FeeItem>, changes: &[WalletChange]) -> Vec<FeeItem> {
+    let mut received: HashMap<PublicKey, i64> = changes
+        .iter()
+        .map(|change| (change.wallet, change.coins))
+        .collect();
+    let mut charged = Vec::new();
+    for item in fees.into_iter().filter(|item| item.reason == FeeReason::Blockchain) {
+        let left = received.entry(item.recipient).or_insert(0);
+        if *left < item.amount as i64 {
+            break;
+        }
+        *left -= item.amount as i64;
+        charged.push(item);
+    }
+    charged
+}
+
+/// Execute `process` for the transaction and record its effects: the
+/// wallets it stored and the fees it charged.
+pub fn capture<T, F>(view: &mut Fork, tx: &T, process: F) -> Result<(), Error>
+where
+    T: Message + FeesCalculator,
+    F: FnOnce(&mut Fork) -> Result<(), Error>,
+{
+    let message_type = tx.raw().message_type();
+    let tx_type = TxType::from_message_type(message_type);
+    let height = blockchain::Schema::new(&*view).block_hashes_by_height().len();
+    let _context = logging::enter(logging::Context {
+        height: Some(height),
+        tx_hash: Some(tx.hash()),
+        tx_type,
+        ..Default::default()
+    });
+
+    let participants = Participants::from_raw(tx.raw().clone()).unwrap_or_default();
+    let mut fees = tx.itemize_fees(view).unwrap_or_default();
+
+    let recorder = Recorder::start();
+    let result = process(view);
+    let recording = recorder.finish();
+
+    let mut changes: Vec<WalletChange> = recording
+        .wallets
+        .iter()
+        .filter_map(|&(ref pub_key, ref before)| {
+            let after = wallet::Schema(&*view).fetch(pub_key);
+            wallet_change(pub_key, before, &after)
+        })
+        .collect();
+    // Participants first, in the order of the transaction.
+    changes.sort_by_key(|change| {
+        participants
+            .wallets
+            .iter()
+            .position(|pub_key| *pub_key == change.wallet)
+            .unwrap_or_else(|| participants.wallets.len())
+    });
+
+    if message_type == BID_OFFER_ID || message_type == ASK_OFFER_ID {
+        fees.retain(|item| item.reason == FeeReason::Blockchain);
+        fees.extend(recording.fees);
+        sort_fee_items(&mut fees);
+    }
+    if result.is_err() {
+        fees = charged_before_failure(fees, &changes);
+    }
+
+    let logged = if metrics::is_dry_run() { None } else { tx_type };
+    if let Some(tx_type) = logged {
+        metrics::observe_size(tx_type, tx.raw().len());
+        if result.is_ok() || !fees.is_empty() {
+            metrics::observe_fees(tx_type, fees.iter().map(|item| item.amount).sum());
+        }
+
+        let outcome = match result {
+            Ok(()) => "ok",
+            Err(ref e) => e.name(),
+        };
+        let _result = logging::enter(logging::Context {
+            result: Some(outcome),
+            ..Default::default()
+        });
+        info!("Executed {} {}: {}", tx_type.name(), tx.hash().to_hex(), outcome);
+    }
+
+    let effects = TxEffects { changes, fees };
+    Schema(&mut *view).store(height, tx.hash(), &effects);
+
+    result
+}
+
+/// Assemble the commit event of a transaction from its stored status and
+/// effects. Returns `None` for transactions of other services.
+pub fn transaction_committed<S: AsRef<Snapshot>>(
+    view: S,
+    raw: RawTransaction,
+    height: u64,
+    index: u64,
+) -> Option<TransactionCommitted> {
+    let tx_hash = raw.hash();
+    let tx_type = TxType::from_message_type(raw.message_type())?;
+    let participants = Participants::from_raw(raw)?;
+    let result = status::Schema(view.as_ref()).fetch(&tx_hash)?;
+    let effects = Schema(view.as_ref()).fetch(&tx_hash).unwrap_or_default();
+
+    Some(TransactionCommitted {
+        version: v1::VERSION,
+        tx_hash,
+        height,
+        index,
+        tx_type,
+        wallets: participants.wallets,
+        assets: participants.assets,
+        changes: effects.changes,
+        fees: effects.fees,
+        result: result.into(),
+    })
+}
+
+/// Notification pushed to subscribers on every commit.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A new block was committed.
+    Block(BlockCommitted),
+    /// A transaction was committed.
+    Transaction(TransactionCommitted),
+}
+
+impl Event {
+    /// Name of the event, as used in the `event:` field of the stream.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Event::Block(_) => "block",
+            Event::Transaction(_) => "transaction",
+        }
+    }
+}
+
+/// Subscriber-side selection of transaction events. Block events are
+/// delivered regardless of the filter.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Filter {
+    pub wallet: Option<PublicKey>,
+    pub asset: Option<AssetId>,
+    pub tx_type: Option<TxType>,
+}
+
+impl Filter {
+    /// Checks whether the event passes the filter.
+    pub fn matches(&self, event: &Event) -> bool {
+        match *event {
+            Event::Block(_) => true,
+            Event::Transaction(ref tx) => {
+                self.tx_type.map_or(true, |t| t == tx.tx_type)
+                    && self.wallet.map_or(true, |w| tx.wallets.contains(&w))
+                    && self.asset.map_or(true, |a| tx.assets.contains(&a))
+            }
+        }
+    }
+}
+
+lazy_static! {
+    static ref SUBSCRIBERS: Mutex<Vec<(Filter, SyncSender<Event>)>> = Mutex::new(Vec::new());
+}
+
+/// Register a new subscriber. Returns `None` when `max_subscribers()` are
+/// already connected.
+pub fn subscribe(filter: Filter) -> Option<Receiver<Event>> {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    if subscribers.len() >= max_subscribers() {
+        return None;
+    }
+    let (sender, receiver) = mpsc::sync_channel(SUBSCRIBER_BUFFER);
+    subscribers.push((filter, sender));
+    Some(receiver)
+}
+
+/// Deliver events to every subscriber whose filter accepts them.
+/// Disconnected subscribers and the ones that can't keep up are dropped.
+pub fn broadcast(events: &[Event]) {
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.retain(|&(ref filter, ref sender)| {
+        for event in events.iter().filter(|e| filter.matches(e)) {
+            match sender.try_send(event.clone()) {
+                Ok(_) => (),
+                Err(TrySendError::Full(_)) => {
+                    warn!("Dropping events subscriber that can't keep up.");
+                    return false;
+                }
+                Err(TrySendError::Disconnected(_)) => return false,
+            }
+        }
+        true
+    });
+}
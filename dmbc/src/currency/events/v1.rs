@@ -0,0 +1,152 @@
+//! Version 1 of the commit event format.
+//!
+//! These are the messages published to event sinks and the event stream
+//! after every commit. Clients may deserialize them with this module; fields
+//! are only ever added within a version, and incompatible changes get a new
+//! module and a new `version` value.
+
+use exonum::crypto::{Hash, PublicKey};
+
+use currency::assets::AssetId;
+use currency::error::Error;
+use currency::transactions::components::FeeItem;
+use currency::transactions::{
+    ADD_ASSETS_ID, ASK_OFFER_ID, BID_OFFER_ID, DELETE_ASSETS_ID, EXCHANGE_ID,
+    EXCHANGE_INTERMEDIARY_ID, TRADE_ID, TRADE_INTERMEDIARY_ID, TRANSFER_FEES_PAYER_ID, TRANSFER_ID,
+};
+
+/// Value of the `version` field of every event in this module.
+pub const VERSION: u16 = 1;
+
+/// Kind of a cryptocurrency transaction.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TxType {
+    Transfer,
+    TransferWithFeesPayer,
+    AddAssets,
+    DeleteAssets,
+    Trade,
+    TradeIntermediary,
+    Exchange,
+    ExchangeIntermediary,
+    BidOffer,
+    AskOffer,
+}
+
+impl TxType {
+    /// Transaction type for the given message type identifier.
+    pub fn from_message_type(message_type: u16) -> Option<TxType> {
+        match message_type {
+            TRANSFER_ID => Some(TxType::Transfer),
+            TRANSFER_FEES_PAYER_ID => Some(TxType::TransferWithFeesPayer),
+            ADD_ASSETS_ID => Some(TxType::AddAssets),
+            DELETE_ASSETS_ID => Some(TxType::DeleteAssets),
+            TRADE_ID => Some(TxType::Trade),
+            TRADE_INTERMEDIARY_ID => Some(TxType::TradeIntermediary),
+            EXCHANGE_ID => Some(TxType::Exchange),
+            EXCHANGE_INTERMEDIARY_ID => Some(TxType::ExchangeIntermediary),
+            BID_OFFER_ID => Some(TxType::BidOffer),
+            ASK_OFFER_ID => Some(TxType::AskOffer),
+            _ => None,
+        }
+    }
+
+    /// The `snake_case` name of the transaction type.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            TxType::Transfer => "transfer",
+            TxType::TransferWithFeesPayer => "transfer_with_fees_payer",
+            TxType::AddAssets => "add_assets",
+            TxType::DeleteAssets => "delete_assets",
+            TxType::Trade => "trade",
+            TxType::TradeIntermediary => "trade_intermediary",
+            TxType::Exchange => "exchange",
+            TxType::ExchangeIntermediary => "exchange_intermediary",
+            TxType::BidOffer => "bid_offer",
+            TxType::AskOffer => "ask_offer",
+        }
+    }
+
+    /// Parse the `snake_case` name of a transaction type.
+    pub fn from_name(name: &str) -> Option<TxType> {
+        ::serde_json::from_value(json!(name)).ok()
+    }
+}
+
+/// Change in the amount of a single asset held by a wallet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AssetChange {
+    pub id: AssetId,
+    pub amount: i64,
+}
+
+/// Coins and assets that entered (positive) or left (negative) a wallet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct WalletChange {
+    pub wallet: PublicKey,
+    pub coins: i64,
+    pub assets: Vec<AssetChange>,
+}
+
+/// Outcome of the transaction execution.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TxResult {
+    /// `0` on success, otherwise the numeric value of `currency::error::Error`.
+    pub code: u8,
+    /// Human readable description of the code.
+    pub description: String,
+}
+
+impl TxResult {
+    /// Result code is zero.
+    pub fn is_success(&self) -> bool {
+        self.code == 0
+    }
+}
+
+impl From<Result<(), Error>> for TxResult {
+    fn from(result: Result<(), Error>) -> Self {
+        match result {
+            Ok(()) => TxResult {
+                code: 0,
+                description: "success".to_string(),
+            },
+            Err(e) => TxResult {
+                code: e as u8,
+                description: e.to_string(),
+            },
+        }
+    }
+}
+
+/// A block was committed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BlockCommitted {
+    pub version: u16,
+    pub height: u64,
+    pub hash: Hash,
+    pub tx_count: u32,
+}
+
+/// A transaction was committed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TransactionCommitted {
+    pub version: u16,
+    pub tx_hash: Hash,
+    /// Height of the block containing the transaction.
+    pub height: u64,
+    /// Position of the transaction in the block.
+    pub index: u64,
+    pub tx_type: TxType,
+    /// Wallets named in the transaction.
+    pub wallets: Vec<PublicKey>,
+    /// Assets named in the transaction.
+    pub assets: Vec<AssetId>,
+    /// Balance changes of every affected wallet, fees included.
+    pub changes: Vec<WalletChange>,
+    /// Fees charged. A failed transaction keeps the blockchain fees charged
+    /// before it failed, and has no others.
+    pub fees: Vec<FeeItem>,
+    pub result: TxResult,
+}
@@ -0,0 +1,418 @@
+//! Initial state of the network.
+//!
+//! A genesis file describes everything committed into the genesis block:
+//! validators, consensus parameters, the initial service configuration and
+//! the initial wallets. It is read from TOML, or from JSON if the file name
+//! ends with `.json`. Numbers are plain numbers, keys are hex strings and
+//! fee fractions are decimal strings such as `"0.01"`:
+//!
+//! ```toml
+//! [[validators]]
+//! consensus_key = "4e29...1b9f"
+//! service_key = "68e7...e9f0"
+//!
+//! [consensus]
+//! round_timeout = 3500
+//! status_timeout = 5000
+//! peers_timeout = 10000
+//! txs_block_limit = 3000
+//! max_message_len = 1048576
+//! timeout_adjuster = { type = "Constant", timeout = 2500 }
+//!
+//! [fees]
+//! recipient = "36a0...6a61"
+//! transfer = 1000
+//!
+//! [permissions]
+//! global_permission_mask = "0xffffffffffffffff"
+//!
+//! [[wallets]]
+//! pub_key = "36a0...6a61"
+//! balance = 100000000
+//!
+//! [[wallets.assets]]
+//! data = "gold"
+//! amount = 10
+//! fees = { transfer = { fixed = 10, fraction = "0.0" } }
+//! ```
+
+extern crate toml;
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+use exonum::blockchain::{ConsensusConfig, TimeoutAdjusterConfig, ValidatorKeys};
+use exonum::crypto::{self, PublicKey};
+use exonum::encoding::serialize::FromHex;
+use exonum::storage::Fork;
+use serde_json;
+
+use config;
+use currency::assets;
+use currency::assets::{AssetBundle, AssetId, AssetInfo, Fee, Fees, ASSET_DATA_MAX_LENGTH};
+use currency::configuration::{
+    Configuration, TransactionFees, TransactionPermissions, WalletPermissions,
+    GENESIS_WALLET_PUB_KEY,
+};
+use currency::supply;
+use currency::transactions::components::permissions;
+use currency::wallet;
+use currency::wallet::Wallet;
+use decimal::UFract64;
+
+const GENESIS_VALIDATOR_PUBLIC: &str =
+    "4e298e435018ab0a1430b6ebd0a0656be15493966d5ce86ed36416e24c411b9f";
+const GENESIS_SERVICE_PUBLIC: &str =
+    "68e774a4339cccfae644dcf3e44360839c84a6475c7d2943ed59b81d7eb6e9f0";
+
+/// Fixed fees paid to the fee recipient, see `TransactionFees`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GenesisFees {
+    pub recipient: PublicKey,
+    #[serde(default)]
+    pub add_assets: u64,
+    #[serde(default)]
+    pub add_assets_per_entry: u64,
+    #[serde(default)]
+    pub delete_assets: u64,
+    #[serde(default)]
+    pub exchange: u64,
+    #[serde(default)]
+    pub trade: u64,
+    #[serde(default)]
+    pub transfer: u64,
+}
+
+/// Permission masks are written as `0x` prefixed hex strings, since TOML
+/// integers can't hold all 64 bits. Plain numbers are accepted as well.
+mod mask {
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::Serializer;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Number(u64),
+        Hex(String),
+    }
+
+    pub fn serialize<S: Serializer>(mask: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:#018x}", mask))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(mask) => Ok(mask),
+            Repr::Hex(s) => u64::from_str_radix(s.trim_left_matches("0x"), 16)
+                .map_err(|_| de::Error::custom(format!("`{}` is not a hex mask", s))),
+        }
+    }
+}
+
+/// Permission mask of a single wallet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GenesisWalletPermissions {
+    pub key: PublicKey,
+    #[serde(with = "mask")]
+    pub mask: u64,
+}
+
+/// Transaction permissions, see `TransactionPermissions`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GenesisPermissions {
+    #[serde(with = "mask", default = "all_allowed")]
+    pub global_permission_mask: u64,
+    #[serde(default)]
+    pub wallets: Vec<GenesisWalletPermissions>,
+}
+
+fn all_allowed() -> u64 {
+    permissions::ALL_ALLOWED_MASK
+}
+
+impl Default for GenesisPermissions {
+    fn default() -> Self {
+        GenesisPermissions {
+            wallets: Vec::new(),
+            global_permission_mask: all_allowed(),
+        }
+    }
+}
+
+/// Third party fee of an asset for one kind of operation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GenesisFee {
+    #[serde(default)]
+    pub fixed: u64,
+    #[serde(default = "zero_fraction")]
+    pub fraction: String,
+}
+
+fn zero_fraction() -> String {
+    "0.0".to_string()
+}
+
+impl Default for GenesisFee {
+    fn default() -> Self {
+        GenesisFee {
+            fixed: 0,
+            fraction: zero_fraction(),
+        }
+    }
+}
+
+impl GenesisFee {
+    /// Describe an existing fee.
+    pub fn from_fee(fee: &Fee) -> Self {
+        GenesisFee {
+            fixed: fee.fixed(),
+            fraction: fee.fraction().to_string(),
+        }
+    }
+
+    /// The fee, or `None` if the fraction is not a decimal.
+    pub fn to_fee(&self) -> Option<Fee> {
+        let fraction: UFract64 = self.fraction.parse().ok()?;
+        Some(Fee::new(self.fixed, fraction))
+    }
+}
+
+/// Third party fees of an asset, see `Fees`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct GenesisAssetFees {
+    #[serde(default)]
+    pub trade: GenesisFee,
+    #[serde(default)]
+    pub exchange: GenesisFee,
+    #[serde(default)]
+    pub transfer: GenesisFee,
+}
+
+impl GenesisAssetFees {
+    /// Describe existing asset fees.
+    pub fn from_fees(fees: &Fees) -> Self {
+        GenesisAssetFees {
+            trade: GenesisFee::from_fee(&fees.trade()),
+            exchange: GenesisFee::from_fee(&fees.exchange()),
+            transfer: GenesisFee::from_fee(&fees.transfer()),
+        }
+    }
+
+    /// The fees, or `None` if a fraction is not a decimal.
+    pub fn to_fees(&self) -> Option<Fees> {
+        Some(Fees::new(
+            self.trade.to_fee()?,
+            self.exchange.to_fee()?,
+            self.transfer.to_fee()?,
+        ))
+    }
+}
+
+/// Asset held by a genesis wallet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GenesisAsset {
+    /// Asset data; the asset identifier is derived from it and the creator.
+    pub data: String,
+    pub amount: u64,
+    /// Creator of the asset, the owning wallet if omitted.
+    #[serde(default)]
+    pub creator: Option<PublicKey>,
+    #[serde(default)]
+    pub fees: GenesisAssetFees,
+}
+
+/// Wallet existing from the genesis block.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct GenesisWallet {
+    pub pub_key: PublicKey,
+    #[serde(default)]
+    pub balance: u64,
+    #[serde(default)]
+    pub assets: Vec<GenesisAsset>,
+}
+
+/// Contents of a genesis file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Genesis {
+    pub validators: Vec<ValidatorKeys>,
+    pub consensus: ConsensusConfig,
+    pub fees: GenesisFees,
+    #[serde(default)]
+    pub permissions: GenesisPermissions,
+    #[serde(default)]
+    pub wallets: Vec<GenesisWallet>,
+}
+
+/// Consensus parameters of the public network.
+pub fn default_consensus() -> ConsensusConfig {
+    ConsensusConfig {
+        round_timeout: 3500,
+        status_timeout: 5000,
+        peers_timeout: 10_000,
+        txs_block_limit: 3000,
+        max_message_len: ConsensusConfig::DEFAULT_MESSAGE_MAX_LEN,
+        timeout_adjuster: TimeoutAdjusterConfig::Constant { timeout: 2500 },
+    }
+}
+
+impl Default for Genesis {
+    /// Genesis of the public network.
+    fn default() -> Self {
+        let genesis_wallet = PublicKey::from_hex(GENESIS_WALLET_PUB_KEY).unwrap();
+        Genesis {
+            validators: vec![ValidatorKeys {
+                consensus_key: PublicKey::from_hex(GENESIS_VALIDATOR_PUBLIC).unwrap(),
+                service_key: PublicKey::from_hex(GENESIS_SERVICE_PUBLIC).unwrap(),
+            }],
+            consensus: default_consensus(),
+            fees: GenesisFees {
+                recipient: genesis_wallet,
+                add_assets: 0,
+                add_assets_per_entry: 0,
+                delete_assets: 0,
+                exchange: 0,
+                trade: 0,
+                transfer: 0,
+            },
+            permissions: GenesisPermissions::default(),
+            wallets: vec![GenesisWallet {
+                pub_key: genesis_wallet,
+                balance: supply::GENESIS_SUPPLY,
+                assets: Vec::new(),
+            }],
+        }
+    }
+}
+
+fn invalid_data<E: ToString>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+impl Genesis {
+    /// Read a genesis file, TOML unless the name ends with `.json`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Genesis> {
+        let path = path.as_ref();
+        let mut content = String::new();
+        File::open(path)?.read_to_string(&mut content)?;
+        if path.extension().map_or(false, |e| e == "json") {
+            serde_json::from_str(&content).map_err(invalid_data)
+        } else {
+            toml::from_str(&content).map_err(invalid_data)
+        }
+    }
+
+    /// Render the genesis in TOML.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("Genesis is always representable in TOML")
+    }
+
+    /// Check the genesis for problems that would make the network unusable.
+    /// Every problem found is returned.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.validators.is_empty() {
+            errors.push("validators: at least one validator is required".to_string());
+        }
+        let mut keys = HashSet::new();
+        for (i, v) in self.validators.iter().enumerate() {
+            if !keys.insert(v.consensus_key) || !keys.insert(v.service_key) {
+                errors.push(format!("validators[{}]: key is used more than once", i));
+            }
+        }
+
+        errors.extend(config::consensus_errors(&self.consensus).iter().map(|e| e.to_string()));
+
+        let mut wallets = HashSet::new();
+        let mut total: u64 = 0;
+        for (i, w) in self.wallets.iter().enumerate() {
+            if !wallets.insert(w.pub_key) {
+                errors.push(format!("wallets[{}].pub_key: wallet is listed more than once", i));
+            }
+            total = match total.checked_add(w.balance) {
+                Some(total) => total,
+                None => {
+                    errors.push(format!("wallets[{}].balance: total supply overflows", i));
+                    total
+                }
+            };
+            for (j, a) in w.assets.iter().enumerate() {
+                if a.data.len() > ASSET_DATA_MAX_LENGTH {
+                    errors.push(format!("wallets[{}].assets[{}].data: too long", i, j));
+                }
+                if a.amount == 0 {
+                    errors.push(format!("wallets[{}].assets[{}].amount: must be positive", i, j));
+                }
+                if a.fees.to_fees().is_none() {
+                    errors.push(format!(
+                        "wallets[{}].assets[{}].fees: fraction is not a decimal",
+                        i, j
+                    ));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Initial configuration of the cryptocurrency service.
+    pub fn configuration(&self) -> Configuration {
+        let f = &self.fees;
+        let fees = TransactionFees::new(
+            &f.recipient,
+            f.add_assets,
+            f.add_assets_per_entry,
+            f.delete_assets,
+            f.exchange,
+            f.trade,
+            f.transfer,
+        );
+        let wallets = self
+            .permissions
+            .wallets
+            .iter()
+            .map(|w| WalletPermissions::new(&w.key, w.mask))
+            .collect();
+        let permissions =
+            TransactionPermissions::new(wallets, self.permissions.global_permission_mask);
+        Configuration::new(fees, permissions)
+    }
+
+    /// Store the initial wallets, assets and coin supply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the genesis does not pass `validate`.
+    pub fn store(&self, fork: &mut Fork) {
+        let mut total = 0;
+        for w in &self.wallets {
+            let mut bundles = Vec::new();
+            for a in &w.assets {
+                let creator = a.creator.unwrap_or(w.pub_key);
+                let id = AssetId::from_data(&a.data, &creator);
+                let fees = a.fees.to_fees().expect("Invalid genesis asset fees");
+                let origin = crypto::hash(a.data.as_bytes());
+                let info = AssetInfo::new(&creator, &origin, a.amount, fees, &a.data);
+                let info = match assets::Schema(&*fork).fetch(&id) {
+                    Some(existing) => existing.merge(info).expect("Conflicting genesis assets"),
+                    None => info,
+                };
+                assets::Schema(&mut *fork).store(&id, info);
+                bundles.push(AssetBundle::new(id, a.amount));
+            }
+
+            let mut wallet = Wallet::new(w.balance, Vec::new());
+            wallet.add_assets(bundles);
+            wallet::Schema(&mut *fork).store(&w.pub_key, wallet);
+            total += w.balance;
+        }
+        supply::Schema(&mut *fork).store(total);
+    }
+}
@@ -2,14 +2,20 @@
 
 pub mod api;
 pub mod assets;
+pub mod audit;
+pub mod backup;
 pub mod configuration;
 pub mod error;
+pub mod events;
+pub mod genesis;
+pub mod state;
 pub mod status;
+pub mod supply;
 pub mod transactions;
 pub mod wallet;
 pub mod offers;
+pub mod sinks;
 
-mod nats;
 mod service;
 
 pub use currency::service::{Service, SERVICE_ID, SERVICE_NAME};
@@ -14,7 +14,8 @@ pub use currency::offers::history::{HistoryOffer, HistoryOffers};
 
 use exonum::crypto::{PublicKey, Hash};
 use exonum::storage::Fork;
-use currency::transactions::components::ThirdPartyFees;
+use currency::events;
+use currency::transactions::components::{FeeItem, FeeReason, ThirdPartyFees};
 use currency::assets::{AssetBundle,TradeAsset};
 use currency::wallet;
 use currency::wallet::Wallet;
@@ -83,6 +84,7 @@ pub fn close_bids(
             sum_fee_coins += fee_coins;
             let wallet = wallets.entry(pk).or_insert(wallet::Schema(view).fetch(&pk));
             *wallet = Wallet::new(wallet.balance() + fee_coins, wallet.assets());
+            events::fee_charged(FeeItem::new(&bid.wallet, &pk, FeeReason::CreatorRoyalty, fee_coins));
         }
 
 
@@ -107,6 +109,7 @@ pub fn close_asks(
     asset: &TradeAsset,
     bid: &mut Offer,
     buyer: &mut Wallet,
+    buyer_key: &PublicKey,
 ) -> (HashMap<PublicKey, Wallet>, Vec<HistoryOffer>)
 {
     let mut wallets = HashMap::new();
@@ -137,6 +140,9 @@ pub fn close_asks(
         sum_fee_coins += fee_coins;
         let wallet = wallets.entry(pk).or_insert(wallet::Schema(view).fetch(&pk));
         *wallet = Wallet::new(wallet.balance() + fee_coins, wallet.assets());
+        if pk != *buyer_key {
+            events::fee_charged(FeeItem::new(buyer_key, &pk, FeeReason::CreatorRoyalty, fee_coins));
+        }
     }
 
     *buyer = Wallet::new(buyer.balance() + coins - sum_fee_coins, buyer.assets());
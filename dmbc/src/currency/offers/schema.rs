@@ -2,6 +2,7 @@ use exonum::storage::{Fork, MapIndex, Snapshot};
 
 use currency::assets::AssetId;
 use currency::offers::OpenOffers;
+use currency::supply;
 use currency::SERVICE_NAME;
 
 /// The schema for accessing wallets data.
@@ -34,16 +35,30 @@ impl<'a> Schema<&'a mut Fork> {
         MapIndex::new(key, &mut *self.0)
     }
 
-    /// Store the new state for a wallet in the database.
+    /// Store the new state for a wallet in the database, recording the
+    /// coins escrowed in the coin supply.
     pub fn store(&mut self, asset_id: &AssetId, open_offers: OpenOffers) {
         match (open_offers.bids().len(), open_offers.asks().len()) {
             (0, 0) => self.remove(asset_id),
-            (_, _) => self.index_mut().put(asset_id, open_offers),
+            (_, _) => {
+                let previous = Schema(&*self.0).fetch(asset_id);
+                self.update_escrowed(&previous, &open_offers);
+                self.index_mut().put(asset_id, open_offers)
+            }
         };
     }
 
-    /// Remove wallet state from the database.
+    /// Remove wallet state from the database, recording the coins released
+    /// in the coin supply.
     pub fn remove(&mut self, asset_id: &AssetId) {
+        let previous = Schema(&*self.0).fetch(asset_id);
+        self.update_escrowed(&previous, &OpenOffers::new_open_offers());
         self.index_mut().remove(asset_id);
     }
+
+    fn update_escrowed(&mut self, before: &OpenOffers, after: &OpenOffers) {
+        let before = supply::escrowed(before).unwrap_or_else(u64::max_value);
+        let after = supply::escrowed(after).unwrap_or_else(u64::max_value);
+        supply::Schema(&mut *self.0).update_escrowed(before, after);
+    }
 }
\ No newline at end of file
@@ -3,31 +3,37 @@ use exonum::blockchain;
 use exonum::blockchain::{ApiContext, ServiceContext, Transaction};
 use exonum::crypto::{Hash, PublicKey};
 use exonum::encoding;
-use exonum::encoding::serialize::FromHex;
 use exonum::messages::Message;
 use exonum::messages::RawTransaction;
 use exonum::storage::Fork;
+use exonum::storage::RocksDB;
 use exonum::storage::Snapshot;
-use iron::Handler;
+use exonum::storage::StorageValue;
+use iron::{Chain, Handler};
 use prometheus::IntGauge;
 use router::Router;
 use std::sync::RwLock;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
-use super::nats;
 use config;
-use currency::api::ServiceApi;
-use currency::configuration;
+use currency::api::auth::ApiKeyAuth;
+use currency::api::limits::{BodyLimit, RateLimit};
+use currency::api::{PrivateApi, ServiceApi};
 use currency::configuration::Configuration;
-use currency::status;
+use currency::events;
+use currency::events::{BlockCommitted, Event};
+use currency::genesis::Genesis;
+use currency::sinks;
+use currency::state::Dump;
+use currency::supply;
 use currency::transactions::{
     AddAssets, DeleteAssets, Exchange, ExchangeIntermediary, Trade, TradeIntermediary, Transfer, TransferWithFeesPayer,
     BidOffer, AskOffer,
     ADD_ASSETS_ID, DELETE_ASSETS_ID, EXCHANGE_ID, EXCHANGE_INTERMEDIARY_ID, TRADE_ID,
     TRADE_INTERMEDIARY_ID, TRANSFER_ID, TRANSFER_FEES_PAYER_ID, BID_OFFER_ID, ASK_OFFER_ID
 };
-use currency::wallet;
-use currency::wallet::Wallet;
+use logging;
 use serde_json;
 
 /// Service identifier.
@@ -37,12 +43,44 @@ pub const SERVICE_ID: u16 = 2;
 pub const SERVICE_NAME: &str = "cryptocurrency";
 
 /// Service data.
-pub struct Service();
+pub struct Service {
+    genesis: Genesis,
+    database: Option<RocksDB>,
+    state: Option<Dump>,
+}
 
 impl Service {
-    /// Create a new cryptocurrency service.
+    /// Create a new cryptocurrency service starting from the genesis of the
+    /// public network.
     pub fn new() -> Self {
-        Service()
+        Service::with_genesis(Genesis::default())
+    }
+
+    /// Create a new cryptocurrency service starting from the given genesis.
+    pub fn with_genesis(genesis: Genesis) -> Self {
+        Service {
+            genesis,
+            database: None,
+            state: None,
+        }
+    }
+
+    /// Serve checkpoints of `database`, the one the node runs on, from the
+    /// private API.
+    pub fn with_backups(self, database: RocksDB) -> Self {
+        Service {
+            database: Some(database),
+            ..self
+        }
+    }
+
+    /// Seed the genesis block with a state dump, next to the genesis
+    /// wallets. The dump must pass `Dump::validate` against the genesis.
+    pub fn with_state(self, state: Dump) -> Self {
+        Service {
+            state: Some(state),
+            ..self
+        }
     }
 
     /// Genesis wallet public key.
@@ -60,6 +98,11 @@ lazy_static! {
     pub static ref CONFIGURATION: RwLock<Configuration> = RwLock::new(Configuration::default());
     pub static ref PERMISSIONS: RwLock<HashMap<PublicKey, u64>> = RwLock::new(HashMap::new());
     static ref CONFIG_HASH: RwLock<Option<Hash>> = RwLock::new(None);
+    static ref SUPPLY_DISCREPANCY: IntGauge = register_int_gauge!(
+        "dmbc_supply_discrepancy_coins",
+        "Difference between the coin supply and the coins held by wallets and open offers, \
+         as of the last block."
+    ).unwrap();
 }
 
 impl blockchain::Service for Service {
@@ -101,14 +144,49 @@ impl blockchain::Service for Service {
         let api = ServiceApi {
             channel: ctx.node_channel().clone(),
             blockchain: ctx.blockchain().clone(),
+            tx_pool: ctx.tx_pool().cloned(),
+            node_state: ctx.node_state().cloned(),
+            service_key: *ctx.public_key(),
+        };
+        api.wire(&mut router);
+
+        let limits = config::config().limits();
+        let auth = config::config().auth();
+        let mut chain = Chain::new(router);
+        chain.link_before(RateLimit::new(
+            limits.clone().per_ip(),
+            limits.clone().routes(),
+            limits.clone().trusted_proxies(),
+        ));
+        chain.link_before(BodyLimit::new(limits.max_body_size()));
+        chain.link_before(ApiKeyAuth::by_method(
+            auth.clone().public_keys(),
+            auth.transactions_keys(),
+        ));
+        Some(Box::new(chain))
+    }
+
+    fn private_api_handler(&self, _ctx: &ApiContext) -> Option<Box<Handler>> {
+        let mut router = Router::new();
+        let api = PrivateApi {
+            database: self.database.clone(),
+            backup_dir: PathBuf::from(config::config().db().backup_dir()),
         };
         api.wire(&mut router);
-        Some(Box::new(router))
+
+        let mut chain = Chain::new(router);
+        chain.link_before(BodyLimit::new(config::config().limits().max_body_size()));
+        chain.link_before(ApiKeyAuth::new(config::config().auth().private_keys()));
+        Some(Box::new(chain))
     }
 
     fn handle_commit(&self, ctx: &ServiceContext) {
         let schema = blockchain::Schema::new(ctx.snapshot());
         let last_block = schema.last_block();
+        let _context = logging::enter(logging::Context {
+            height: Some(last_block.height().0),
+            ..Default::default()
+        });
 
         info!("Block #{}.", last_block.height());
 
@@ -127,21 +205,53 @@ impl blockchain::Service for Service {
             *CONFIG_HASH.write().unwrap() = Some(hash);
         }
 
+        if supply::Schema(ctx.snapshot()).exists() {
+            let supply = supply::Schema(ctx.snapshot()).totals();
+            SUPPLY_DISCREPANCY.set(supply.discrepancy());
+            if !supply.is_conserved() {
+                error!(
+                    "Coin supply is not conserved at block #{}: {:?}",
+                    last_block.height(),
+                    supply
+                );
+            }
+        }
+
+        let height = last_block.height().0;
+        let mut commit_events = vec![Event::Block(BlockCommitted {
+            version: events::v1::VERSION,
+            height,
+            hash: last_block.hash(),
+            tx_count: last_block.tx_count(),
+        })];
+
+        let queuename = config::config().nats().queuename();
+        let mut published = Vec::new();
         let txs = schema.block_txs(last_block.height());
-        for hash in txs.iter() {
-            let status = status::Schema(ctx.snapshot()).fetch(&hash);
-            let msg = json!({ "tx_hash": status }).to_string();
-            let queuename = config::config().nats().queuename();
-            nats::publish(queuename, msg);
+        for (index, hash) in txs.iter().enumerate() {
             info!("Made transaction {:?}", hash.to_hex());
+
+            let committed = schema.transactions().get(&hash).and_then(|raw| {
+                events::transaction_committed(ctx.snapshot(), raw, height, index as u64)
+            });
+            if let Some(committed) = committed {
+                let msg = serde_json::to_string(&committed).unwrap();
+                published.push((queuename.clone(), msg));
+                commit_events.push(Event::Transaction(committed));
+            }
         }
+        // All the events of the block are stored with a single synced write.
+        sinks::publish_all(published);
+
+        events::broadcast(&commit_events);
     }
 
     fn initialize(&self, fork: &mut Fork) -> serde_json::Value {
-        let genesis_wallet = PublicKey::from_hex(configuration::GENESIS_WALLET_PUB_KEY).unwrap();
-        let wallet = Wallet::new(56_921_773_17197150, Vec::new());
-        wallet::Schema(fork).store(&genesis_wallet, wallet);
+        self.genesis.store(fork);
+        if let Some(ref state) = self.state {
+            state.store(fork);
+        }
 
-        serde_json::to_value(Configuration::default()).unwrap()
+        serde_json::to_value(self.genesis.configuration()).unwrap()
     }
 }
@@ -0,0 +1,43 @@
+//! Append-only JSON-lines event sink.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+
+use currency::sinks::EventSink;
+
+/// Appends every event as a single line to a file.
+pub struct FileSink {
+    path: String,
+    file: Option<File>,
+}
+
+impl FileSink {
+    /// Create a sink writing to `path`. The file is created if missing.
+    pub fn new(path: String) -> Self {
+        FileSink { path, file: None }
+    }
+
+    fn file(&mut self) -> io::Result<&mut File> {
+        if self.file.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().unwrap())
+    }
+}
+
+impl EventSink for FileSink {
+    fn name(&self) -> String {
+        format!("file {}", self.path)
+    }
+
+    fn publish(&mut self, _subject: &str, msg: &str) -> io::Result<()> {
+        let line = msg.replace('\n', " ") + "\n";
+        let file = self.file()?;
+        file.write_all(line.as_bytes())?;
+        file.flush()
+    }
+}
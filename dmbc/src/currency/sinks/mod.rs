@@ -0,0 +1,304 @@
+//! Delivery of commit events to external consumers.
+//!
+//! Every configured sink gets its own worker thread, so a slow or failing
+//! sink doesn't hold back the others. Events for sinks backed by an
+//! `Outbox` are stored on disk by `publish_all` itself, with one synced
+//! write for all the events of a block, before it returns, and
+//! the worker only delivers and retries them; the other sinks drop events
+//! while their destination is failing. On shutdown, `flush` lets every
+//! worker deliver what is queued before the process exits.
+
+pub mod file;
+pub mod nats;
+pub mod outbox;
+pub mod webhook;
+
+use std::io;
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use config;
+use config::Sink;
+
+pub use self::file::FileSink;
+pub use self::nats::NatsSink;
+pub use self::outbox::Outbox;
+pub use self::webhook::WebhookSink;
+
+/// Destination for commit events.
+pub trait EventSink: Send {
+    /// Human readable name used in logs.
+    fn name(&self) -> String;
+
+    /// Deliver a single event published under `subject`.
+    fn publish(&mut self, subject: &str, msg: &str) -> io::Result<()>;
+}
+
+/// Start a worker for every sink in the node configuration.
+fn from_config(config: config::Config) -> Result<Vec<Pipe>, String> {
+    let mut pipes = Vec::new();
+
+    let nats = config.clone().nats();
+    if nats.clone().enabled() {
+        let sink: Box<EventSink> = Box::new(NatsSink::new(nats.clone().addresses()));
+        let path = nats.outbox_path();
+        let outbox = Outbox::open(&path)
+            .map_err(|e| format!("Unable to open the NATS outbox at {}: {}", path, e))?;
+        pipes.push(Pipe::spawn_durable(sink, outbox));
+    }
+
+    for sink in config.sinks() {
+        let sink: Box<EventSink> = match sink {
+            Sink::File { path } => Box::new(FileSink::new(path)),
+            Sink::Webhook { url, retries } => Box::new(WebhookSink::new(url, retries.unwrap_or(3))),
+        };
+        pipes.push(Pipe::spawn(sink));
+    }
+
+    Ok(pipes)
+}
+
+/// Start the workers of the configured sinks, unless they are running.
+///
+/// Nodes call this on startup, so that an outbox that can't be opened stops
+/// the node instead of losing events. Otherwise the workers are started by
+/// the first `publish`, which panics on such an error.
+pub fn start() -> Result<(), String> {
+    let mut pipes = PIPES.lock().unwrap();
+    if pipes.is_none() {
+        *pipes = Some(from_config(config::config())?);
+    }
+    Ok(())
+}
+
+fn with_pipes<F: FnMut(&Pipe)>(mut f: F) {
+    let mut pipes = PIPES.lock().unwrap();
+    if pipes.is_none() {
+        let started = from_config(config::config()).unwrap_or_else(|e| panic!("{}", e));
+        *pipes = Some(started);
+    }
+    for pipe in pipes.as_ref().unwrap() {
+        f(pipe);
+    }
+}
+
+/// Publish an event to every configured sink. Events for sinks backed by an
+/// `Outbox` are on disk when this returns.
+pub fn publish(subject: String, msg: String) {
+    publish_all(vec![(subject, msg)]);
+}
+
+/// Publish `(subject, msg)` events to every configured sink, in order. Sinks
+/// backed by an `Outbox` store all of them with a single synced write, and
+/// they are on disk when this returns.
+pub fn publish_all(events: Vec<(String, String)>) {
+    if events.is_empty() {
+        return;
+    }
+    with_pipes(|pipe| {
+        let messages = match pipe.outbox {
+            Some(ref outbox) => match outbox.push_all(&events) {
+                Ok(_) => vec![Message::Stored],
+                Err(e) => {
+                    error!("Unable to store {} events in the outbox: {}", events.len(), e);
+                    return;
+                }
+            },
+            None => events.iter().cloned().map(Message::Event).collect(),
+        };
+        for message in messages {
+            if pipe.sender.send(message).is_err() {
+                warn!("Event sink worker has stopped.");
+                return;
+            }
+        }
+    });
+}
+
+/// Deliver the events queued for every sink and stop the workers. Events
+/// published afterwards are dropped, except by sinks backed by an `Outbox`,
+/// which keep them for the next start.
+///
+/// Returns `false` if some worker didn't finish within `timeout`; events of
+/// sinks backed by an `Outbox` are kept on disk and delivered on the next
+/// start.
+pub fn flush(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    let mut acks = Vec::new();
+    with_pipes(|pipe| {
+        let (done, ack) = mpsc::channel();
+        if pipe.sender.send(Message::Stop(done)).is_ok() {
+            acks.push(ack);
+        }
+    });
+
+    let mut flushed = true;
+    for ack in acks {
+        let now = Instant::now();
+        let left = if deadline > now { deadline - now } else { Duration::from_secs(0) };
+        flushed &= ack.recv_timeout(left).is_ok();
+    }
+    flushed
+}
+
+type PublishPair = (String, String);
+
+enum Message {
+    Event(PublishPair),
+    /// Events have been stored in the outbox.
+    Stored,
+    /// Deliver what is queued, stop and acknowledge.
+    Stop(Sender<()>),
+}
+
+lazy_static! {
+    static ref PIPES: Mutex<Option<Vec<Pipe>>> = Mutex::new(None);
+}
+
+const DISCARD_MODE_SECONDS: u64 = 5;
+const RETRY_MIN_SECONDS: u64 = 1;
+const RETRY_MAX_SECONDS: u64 = 60;
+
+enum Mode {
+    Publish,
+    Discard(Instant),
+}
+
+struct Pipe {
+    thread: thread::JoinHandle<()>,
+    sender: Sender<Message>,
+    /// Where `publish` stores the events of a durable sink.
+    outbox: Option<Outbox>,
+}
+
+impl Pipe {
+    fn spawn(sink: Box<EventSink>) -> Pipe {
+        let (sender, receiver) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name(format!("{} sender", sink.name()))
+            .spawn(|| Pipe::work(sink, receiver))
+            .unwrap();
+        Pipe {
+            thread,
+            sender,
+            outbox: None,
+        }
+    }
+
+    fn spawn_durable(sink: Box<EventSink>, outbox: Outbox) -> Pipe {
+        let (sender, receiver) = mpsc::channel();
+        let delivery = outbox.clone();
+        let thread = thread::Builder::new()
+            .name(format!("{} sender", sink.name()))
+            .spawn(|| Pipe::work_durable(sink, delivery, receiver))
+            .unwrap();
+        Pipe {
+            thread,
+            sender,
+            outbox: Some(outbox),
+        }
+    }
+
+    fn work_durable(mut sink: Box<EventSink>, outbox: Outbox, receiver: Receiver<Message>) {
+        // Delay before the next delivery attempt, set while the sink is failing.
+        let mut retry: Option<(Instant, Duration)> = None;
+
+        loop {
+            let received = match retry {
+                Some((at, _)) => {
+                    let now = Instant::now();
+                    let timeout = if at > now { at - now } else { Duration::from_secs(0) };
+                    receiver.recv_timeout(timeout)
+                }
+                None if outbox.is_empty() => {
+                    receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                }
+                None => Err(RecvTimeoutError::Timeout),
+            };
+
+            match received {
+                // Events are stored by `publish`, so there is only
+                // something new to deliver.
+                Ok(Message::Stored) | Ok(Message::Event(_)) => (),
+                Ok(Message::Stop(done)) => {
+                    if let Err(e) = outbox.deliver(&mut *sink) {
+                        warn!(
+                            "{}: {:?}, {} events are kept for the next start.",
+                            sink.name(),
+                            e,
+                            outbox.len()
+                        );
+                    }
+                    let _ = done.send(());
+                    break;
+                }
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if let Some((at, _)) = retry {
+                if Instant::now() < at {
+                    continue;
+                }
+            }
+
+            match outbox.deliver(&mut *sink) {
+                Ok(_) => {
+                    if retry.is_some() {
+                        info!("{} is accepting messages again.", sink.name());
+                    }
+                    retry = None;
+                }
+                Err(e) => {
+                    let delay = retry
+                        .map(|(_, delay)| delay * 2)
+                        .unwrap_or(Duration::from_secs(RETRY_MIN_SECONDS))
+                        .min(Duration::from_secs(RETRY_MAX_SECONDS));
+                    warn!(
+                        "{}: {:?}, {} events pending, retrying in {} seconds.",
+                        sink.name(),
+                        e,
+                        outbox.len(),
+                        delay.as_secs()
+                    );
+                    retry = Some((Instant::now() + delay, delay));
+                }
+            }
+        }
+    }
+
+    fn work(mut sink: Box<EventSink>, receiver: Receiver<Message>) {
+        let mut mode = Mode::Publish;
+        let discard_duration = Duration::from_secs(DISCARD_MODE_SECONDS);
+
+        let mut process_pair = |pair: PublishPair| match mode {
+            Mode::Publish => match sink.publish(&pair.0, &pair.1) {
+                Ok(_) => info!("success published to {}", sink.name()),
+                Err(e) => {
+                    warn!("{}: {:?}", sink.name(), e);
+                    warn!("Discarding messages for {} seconds.", DISCARD_MODE_SECONDS);
+                    mode = Mode::Discard(Instant::now());
+                }
+            },
+            Mode::Discard(begin) if begin.elapsed() < discard_duration => (),
+            Mode::Discard(_) => {
+                info!("Accepting messages again.");
+                mode = Mode::Publish;
+            }
+        };
+
+        for message in receiver {
+            match message {
+                Message::Event(pair) => process_pair(pair),
+                Message::Stored => (),
+                Message::Stop(done) => {
+                    let _ = done.send(());
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! NATS event sink.
+
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+use nats::Client;
+
+use currency::sinks::EventSink;
+
+// Outcome of the last connection or publishing attempt, zero before any.
+const CONNECTED: usize = 1;
+const DISCONNECTED: usize = 2;
+
+static STATUS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// Whether the last attempt to connect or publish to NATS succeeded, or
+/// `None` if nothing was attempted yet.
+pub fn connected() -> Option<bool> {
+    match STATUS.load(Ordering::Relaxed) {
+        CONNECTED => Some(true),
+        DISCONNECTED => Some(false),
+        _ => None,
+    }
+}
+
+/// Publishes events to a NATS cluster. The connection is established on
+/// the first event and re-established after failures.
+pub struct NatsSink {
+    addresses: Vec<String>,
+    client: Option<Client>,
+}
+
+impl NatsSink {
+    /// Create a sink for the given NATS server addresses.
+    pub fn new(addresses: Vec<String>) -> Self {
+        NatsSink {
+            addresses,
+            client: None,
+        }
+    }
+
+    fn client(&mut self) -> io::Result<&mut Client> {
+        if self.client.is_none() {
+            let client = Client::new(self.addresses.clone()).map_err(|e| {
+                STATUS.store(DISCONNECTED, Ordering::Relaxed);
+                io::Error::new(io::ErrorKind::Other, e.to_string())
+            })?;
+            self.client = Some(client);
+        }
+        Ok(self.client.as_mut().unwrap())
+    }
+}
+
+impl EventSink for NatsSink {
+    fn name(&self) -> String {
+        "NATS".to_string()
+    }
+
+    fn publish(&mut self, subject: &str, msg: &str) -> io::Result<()> {
+        let result = self
+            .client()?
+            .publish(subject, msg.as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()));
+        if result.is_err() {
+            self.client = None;
+        }
+        let status = if result.is_ok() { CONNECTED } else { DISCONNECTED };
+        STATUS.store(status, Ordering::Relaxed);
+        result
+    }
+}
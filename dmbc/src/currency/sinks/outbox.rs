@@ -0,0 +1,195 @@
+//! Durable queue of events awaiting delivery.
+//!
+//! Events are stored in a separate RocksDB database under increasing
+//! sequence numbers and removed only after the sink has accepted them, so
+//! delivery is at-least-once: an event may be repeated after a crash but is
+//! never lost. Consumers can deduplicate by the `seq` field.
+//!
+//! Clones share the same queue, so events can be stored by one thread while
+//! another delivers them.
+
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use exonum::storage::{self, Database, Entry, Fork, MapIndex, RocksDB, RocksDBOptions, Snapshot};
+use prometheus::IntGauge;
+use serde_json;
+
+use currency::sinks::EventSink;
+
+lazy_static! {
+    static ref BACKLOG: IntGauge = register_int_gauge!(
+        "dmbc_outbox_backlog_events",
+        "Events stored in the outbox and not yet delivered."
+    ).unwrap();
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    subject: String,
+    msg: String,
+}
+
+struct Schema<S>(S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    fn events(self) -> MapIndex<S, u64, String> {
+        MapIndex::new("outbox.events", self.0)
+    }
+
+    fn next_seq(self) -> u64 {
+        let entry: Entry<S, u64> = Entry::new("outbox.next_seq", self.0);
+        entry.get().unwrap_or(0)
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    fn events_mut(&mut self) -> MapIndex<&mut Fork, u64, String> {
+        MapIndex::new("outbox.events", &mut *self.0)
+    }
+
+    fn set_next_seq(&mut self, seq: u64) {
+        let mut entry: Entry<&mut Fork, u64> = Entry::new("outbox.next_seq", &mut *self.0);
+        entry.set(seq);
+    }
+}
+
+/// Persistent FIFO of events for a single sink.
+#[derive(Clone)]
+pub struct Outbox {
+    db: RocksDB,
+    len: Arc<AtomicUsize>,
+    /// Serializes pushes, which assign the sequence numbers.
+    push_lock: Arc<Mutex<()>>,
+}
+
+impl Outbox {
+    /// Open the outbox stored at `path`, creating it if missing. Events left
+    /// from a previous run are kept and delivered first.
+    pub fn open<P: AsRef<Path>>(path: P) -> storage::Result<Outbox> {
+        let mut options = RocksDBOptions::default();
+        options.create_if_missing(true);
+        let db = RocksDB::open(path, &options)?;
+        let len = Schema(db.snapshot()).events().keys().count();
+        BACKLOG.add(len as i64);
+        Ok(Outbox {
+            db,
+            len: Arc::new(AtomicUsize::new(len)),
+            push_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// Number of events waiting for delivery.
+    pub fn len(&self) -> u64 {
+        self.len.load(Ordering::SeqCst) as u64
+    }
+
+    /// Checks whether all events have been delivered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Durably store an event and return its sequence number. The event is
+    /// on disk when this returns.
+    pub fn push(&self, subject: &str, msg: &str) -> storage::Result<u64> {
+        self.push_all(&[(subject.to_string(), msg.to_string())])
+            .map(|seqs| seqs.start)
+    }
+
+    /// Durably store `(subject, msg)` events with a single synced write and
+    /// return their sequence numbers. The events are on disk when this
+    /// returns.
+    pub fn push_all(&self, events: &[(String, String)]) -> storage::Result<Range<u64>> {
+        let _lock = self.push_lock.lock().unwrap();
+        let mut fork = self.db.fork();
+        let first = Schema(&fork).next_seq();
+        let next = first + events.len() as u64;
+        if events.is_empty() {
+            return Ok(first..next);
+        }
+        {
+            let mut schema = Schema(&mut fork);
+            for (seq, &(ref subject, ref msg)) in (first..next).zip(events) {
+                let record = Record {
+                    subject: subject.clone(),
+                    msg: msg.clone(),
+                };
+                schema
+                    .events_mut()
+                    .put(&seq, serde_json::to_string(&record).unwrap());
+            }
+            schema.set_next_seq(next);
+        }
+        self.db.merge_sync(fork.into_patch())?;
+
+        self.len.fetch_add(events.len(), Ordering::SeqCst);
+        BACKLOG.add(events.len() as i64);
+        Ok(first..next)
+    }
+
+    /// Deliver stored events in order until the outbox is empty or the
+    /// sink fails. Returns the number of delivered events.
+    pub fn deliver(&self, sink: &mut EventSink) -> io::Result<u64> {
+        let snapshot = self.db.snapshot();
+        let mut delivered = Vec::new();
+        let mut result = Ok(());
+
+        for (seq, value) in Schema(&snapshot).events().iter() {
+            let record: Record = match serde_json::from_str(&value) {
+                Ok(record) => record,
+                Err(e) => {
+                    error!("Dropping corrupted outbox event #{}: {}", seq, e);
+                    delivered.push(seq);
+                    continue;
+                }
+            };
+            match sink.publish(&record.subject, &with_sequence(seq, &record.msg)) {
+                Ok(()) => delivered.push(seq),
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if !delivered.is_empty() {
+            let mut fork = self.db.fork();
+            {
+                let mut schema = Schema(&mut fork);
+                let mut events = schema.events_mut();
+                for seq in &delivered {
+                    events.remove(seq);
+                }
+            }
+            self.db
+                .merge_sync(fork.into_patch())
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+            self.len.fetch_sub(delivered.len(), Ordering::SeqCst);
+            BACKLOG.sub(delivered.len() as i64);
+        }
+
+        result.map(|_| delivered.len() as u64)
+    }
+}
+
+/// Add the sequence number to a JSON object message, or wrap any other
+/// message into one.
+pub fn with_sequence(seq: u64, msg: &str) -> String {
+    let value = match serde_json::from_str(msg) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            object.insert("seq".to_string(), json!(seq));
+            serde_json::Value::Object(object)
+        }
+        _ => json!({ "seq": seq, "data": msg }),
+    };
+    value.to_string()
+}
@@ -0,0 +1,82 @@
+//! HTTP webhook event sink.
+
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use hyper::header::{ContentType, Headers};
+use hyper::Client;
+
+use currency::sinks::EventSink;
+
+const TIMEOUT_SECONDS: u64 = 10;
+const BACKOFF_BASE_MILLIS: u64 = 200;
+const BACKOFF_MAX_SHIFT: u32 = 8;
+
+/// POSTs every event as a JSON body, retrying failed deliveries with
+/// exponential backoff. The subject is sent in the `X-Event-Subject` header.
+pub struct WebhookSink {
+    url: String,
+    retries: u32,
+    client: Client,
+}
+
+impl WebhookSink {
+    /// Create a sink posting to `url`, making up to `retries` additional
+    /// attempts per event.
+    pub fn new(url: String, retries: u32) -> Self {
+        let mut client = Client::new();
+        client.set_read_timeout(Some(Duration::from_secs(TIMEOUT_SECONDS)));
+        client.set_write_timeout(Some(Duration::from_secs(TIMEOUT_SECONDS)));
+        WebhookSink {
+            url,
+            retries,
+            client,
+        }
+    }
+
+    fn post(&self, subject: &str, msg: &str) -> io::Result<()> {
+        let mut headers = Headers::new();
+        headers.set(ContentType::json());
+        headers.set_raw("X-Event-Subject", vec![subject.as_bytes().to_vec()]);
+
+        let response = self
+            .client
+            .post(&self.url)
+            .headers(headers)
+            .body(msg)
+            .send()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+
+        if response.status.is_success() {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("webhook responded with {}", response.status),
+            ))
+        }
+    }
+}
+
+impl EventSink for WebhookSink {
+    fn name(&self) -> String {
+        format!("webhook {}", self.url)
+    }
+
+    fn publish(&mut self, subject: &str, msg: &str) -> io::Result<()> {
+        let mut attempt = 0;
+        loop {
+            match self.post(subject, msg) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= self.retries => return Err(e),
+                Err(e) => {
+                    warn!("{}: {}, retrying", self.name(), e);
+                    let shift = attempt.min(BACKOFF_MAX_SHIFT);
+                    thread::sleep(Duration::from_millis(BACKOFF_BASE_MILLIS << shift));
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
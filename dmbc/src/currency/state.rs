@@ -0,0 +1,433 @@
+//! Export and import of the service state.
+//!
+//! A state dump is a JSON-lines file. The first line is a `header` record
+//! with the height and hash of the block the state was taken at; every other
+//! line holds one wallet, asset, order book, offer history or transaction
+//! status. Amounts are plain JSON numbers and keys are hex strings, so a
+//! dump does not depend on the binary encoding of the chain it came from:
+//!
+//! ```text
+//! {"type":"header","version":1,"height":1024,"block_hash":"9a1e...","supply":100000000}
+//! {"type":"wallet","pub_key":"36a0...6a61","balance":5000,"assets":[{"id":"82c1...fa9c","amount":10}]}
+//! {"type":"asset","id":"82c1...fa9c","creator":"36a0...6a61","origin":"5f1b...","amount":10,"fees":{...},"data":"gold"}
+//! ```
+//!
+//! A dump can seed the genesis block of a new chain, next to the wallets of
+//! the genesis file.
+
+use std::collections::HashSet;
+use std::io;
+use std::io::{BufRead, Write};
+
+use exonum::blockchain;
+use exonum::crypto::{Hash, PublicKey};
+use exonum::storage::{Fork, Snapshot};
+use serde_json;
+
+use currency::assets;
+use currency::assets::{AssetBundle, AssetId, AssetInfo};
+use currency::error::Error;
+use currency::genesis::{Genesis, GenesisAssetFees};
+use currency::offers;
+use currency::offers::history;
+use currency::offers::{HistoryOffer, HistoryOffers, Offer, Offers, OpenOffers};
+use currency::status;
+use currency::supply;
+use currency::wallet;
+use currency::wallet::Wallet;
+
+/// Version of the dump format.
+pub const VERSION: u16 = 1;
+
+/// First line of a dump.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Header {
+    pub version: u16,
+    /// Height of the block the state was taken at.
+    pub height: u64,
+    /// Hash of the block the state was taken at.
+    pub block_hash: Hash,
+    /// Total coin supply, including coins escrowed by open offers.
+    pub supply: u64,
+}
+
+/// Amount of an asset held by a wallet.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AssetAmount {
+    pub id: AssetId,
+    pub amount: u64,
+}
+
+/// Open offer at some price.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct OfferRecord {
+    pub wallet: PublicKey,
+    pub amount: u64,
+    pub tx_hash: Hash,
+}
+
+/// Open offers at one price.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PriceLevel {
+    pub price: u64,
+    pub offers: Vec<OfferRecord>,
+}
+
+/// Amount of an offer matched by a transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Fill {
+    pub tx_hash: Hash,
+    pub amount: u64,
+}
+
+/// A line of a dump.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Record {
+    Header(Header),
+    Wallet {
+        pub_key: PublicKey,
+        balance: u64,
+        assets: Vec<AssetAmount>,
+    },
+    Asset {
+        id: AssetId,
+        creator: PublicKey,
+        origin: Hash,
+        amount: u64,
+        fees: GenesisAssetFees,
+        data: String,
+    },
+    OpenOffers {
+        asset_id: AssetId,
+        bids: Vec<PriceLevel>,
+        asks: Vec<PriceLevel>,
+    },
+    OfferHistory { tx_hash: Hash, history: Vec<Fill> },
+    /// Result of a transaction, `0` on success and the error code otherwise.
+    Status { tx_hash: Hash, code: u8 },
+}
+
+fn invalid_data<E: ToString>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+}
+
+fn write_record<W: Write>(out: &mut W, record: &Record) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, record).map_err(invalid_data)?;
+    writeln!(out)
+}
+
+fn price_levels(levels: Vec<Offers>) -> Vec<PriceLevel> {
+    levels
+        .into_iter()
+        .map(|level| PriceLevel {
+            price: level.price(),
+            offers: level
+                .offers()
+                .into_iter()
+                .map(|offer| OfferRecord {
+                    wallet: *offer.wallet(),
+                    amount: offer.amount(),
+                    tx_hash: *offer.tx_hash(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn offers(levels: &[PriceLevel]) -> Vec<Offers> {
+    levels
+        .iter()
+        .map(|level| {
+            let offers = level
+                .offers
+                .iter()
+                .map(|o| Offer::new(&o.wallet, o.amount, &o.tx_hash))
+                .collect();
+            Offers::new(level.price, offers)
+        })
+        .collect()
+}
+
+/// Write the state at the last committed block to `out`.
+pub fn export<W: Write>(view: &Snapshot, mut out: W) -> io::Result<Header> {
+    let schema = blockchain::Schema::new(view);
+    if schema.block_hashes_by_height().len() == 0 {
+        return Err(invalid_data("the database contains no blocks"));
+    }
+    let last_block = schema.last_block();
+    let header = Header {
+        version: VERSION,
+        height: last_block.height().0,
+        block_hash: last_block.hash(),
+        supply: supply::Schema(view).fetch(),
+    };
+    write_record(&mut out, &Record::Header(header.clone()))?;
+
+    for (pub_key, w) in wallet::Schema(view).index().iter() {
+        let assets = w
+            .assets()
+            .into_iter()
+            .map(|a| AssetAmount {
+                id: a.id(),
+                amount: a.amount(),
+            })
+            .collect();
+        let record = Record::Wallet {
+            pub_key,
+            balance: w.balance(),
+            assets,
+        };
+        write_record(&mut out, &record)?;
+    }
+
+    for (id, info) in assets::Schema(view).index().iter() {
+        let record = Record::Asset {
+            id,
+            creator: *info.creator(),
+            origin: *info.origin(),
+            amount: info.amount(),
+            fees: GenesisAssetFees::from_fees(&info.fees()),
+            data: info.data().to_string(),
+        };
+        write_record(&mut out, &record)?;
+    }
+
+    for (asset_id, open_offers) in offers::Schema(view).index().iter() {
+        let record = Record::OpenOffers {
+            asset_id,
+            bids: price_levels(open_offers.bids()),
+            asks: price_levels(open_offers.asks()),
+        };
+        write_record(&mut out, &record)?;
+    }
+
+    for (tx_hash, h) in history::Schema(view).index().iter() {
+        let history = h
+            .history()
+            .into_iter()
+            .map(|fill| Fill {
+                tx_hash: *fill.tx_hash(),
+                amount: fill.amount(),
+            })
+            .collect();
+        write_record(&mut out, &Record::OfferHistory { tx_hash, history })?;
+    }
+
+    for (tx_hash, code) in status::Schema(view).index().iter() {
+        write_record(&mut out, &Record::Status { tx_hash, code })?;
+    }
+
+    out.flush()?;
+    Ok(header)
+}
+
+/// Contents of a dump.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dump {
+    pub header: Header,
+    pub records: Vec<Record>,
+}
+
+impl Dump {
+    /// Read a dump, checking that it starts with a header of a supported
+    /// version.
+    pub fn read<R: BufRead>(input: R) -> io::Result<Dump> {
+        let mut header = None;
+        let mut records = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line)
+                .map_err(|e| invalid_data(format!("line {}: {}", i + 1, e)))?;
+            match (record, header.is_some()) {
+                (Record::Header(h), false) => {
+                    if h.version != VERSION {
+                        return Err(invalid_data(format!(
+                            "unsupported dump version {}",
+                            h.version
+                        )));
+                    }
+                    header = Some(h);
+                }
+                (Record::Header(_), true) => {
+                    return Err(invalid_data(format!("line {}: repeated header", i + 1)))
+                }
+                (_, false) => {
+                    return Err(invalid_data("the dump does not start with a header"))
+                }
+                (record, true) => records.push(record),
+            }
+        }
+        match header {
+            Some(header) => Ok(Dump { header, records }),
+            None => Err(invalid_data("the dump is empty")),
+        }
+    }
+
+    /// Check that the dump can be stored next to the state of `genesis`,
+    /// that its supply matches the coins of its wallets and asks and that
+    /// the total supply fits in `u64`. Every problem found is returned.
+    pub fn validate(&self, genesis: &Genesis) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let mut wallets: HashSet<PublicKey> =
+            genesis.wallets.iter().map(|w| w.pub_key).collect();
+        let mut assets: HashSet<AssetId> = genesis
+            .wallets
+            .iter()
+            .flat_map(|w| {
+                w.assets
+                    .iter()
+                    .map(move |a| AssetId::from_data(&a.data, &a.creator.unwrap_or(w.pub_key)))
+            })
+            .collect();
+        let mut held = HashSet::new();
+        // Coins of the wallets and escrowed by asks, `None` on overflow.
+        let mut coins = Some(0u64);
+
+        for record in &self.records {
+            match *record {
+                Record::Wallet {
+                    ref pub_key,
+                    balance,
+                    assets: ref amounts,
+                } => {
+                    if !wallets.insert(*pub_key) {
+                        errors.push(format!("wallet {}: listed more than once", pub_key.to_hex()));
+                    }
+                    held.extend(amounts.iter().map(|a| a.id));
+                    coins = coins.and_then(|c| c.checked_add(balance));
+                }
+                Record::OpenOffers { ref asks, .. } => {
+                    for level in asks {
+                        for offer in &level.offers {
+                            coins = coins.and_then(|c| {
+                                level
+                                    .price
+                                    .checked_mul(offer.amount)
+                                    .and_then(|escrowed| c.checked_add(escrowed))
+                            });
+                        }
+                    }
+                }
+                Record::Asset { ref id, ref fees, .. } => {
+                    if !assets.insert(*id) {
+                        errors.push(format!("asset {}: listed more than once", id.to_string()));
+                    }
+                    if fees.to_fees().is_none() {
+                        errors.push(format!("asset {}: fraction is not a decimal", id.to_string()));
+                    }
+                }
+                Record::Status { ref tx_hash, code } => {
+                    if code != 0 && Error::try_from(code).is_none() {
+                        errors.push(format!(
+                            "status {}: unknown code {}",
+                            tx_hash.to_hex(),
+                            code
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        for id in held {
+            if !assets.contains(&id) {
+                errors.push(format!("asset {}: held by a wallet but not listed", id.to_string()));
+            }
+        }
+
+        match coins {
+            Some(coins) if coins == self.header.supply => (),
+            Some(coins) => errors.push(format!(
+                "supply {} does not match the {} coins of wallets and asks",
+                self.header.supply, coins
+            )),
+            None => errors.push("coins of wallets and asks overflow".to_string()),
+        }
+
+        let genesis_supply = genesis
+            .wallets
+            .iter()
+            .fold(Some(0u64), |sum, w| sum?.checked_add(w.balance));
+        if genesis_supply.and_then(|s| s.checked_add(self.header.supply)).is_none() {
+            errors.push(format!(
+                "supply {} overflows when added to the genesis supply",
+                self.header.supply
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Store the dumped state and add its coins to the supply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dump does not pass `validate`.
+    pub fn store(&self, fork: &mut Fork) {
+        for record in &self.records {
+            match *record {
+                Record::Header(_) => (),
+                Record::Wallet {
+                    ref pub_key,
+                    balance,
+                    ref assets,
+                } => {
+                    let bundles = assets
+                        .iter()
+                        .map(|a| AssetBundle::new(a.id, a.amount))
+                        .collect();
+                    wallet::Schema(&mut *fork).store(pub_key, Wallet::new(balance, bundles));
+                }
+                Record::Asset {
+                    ref id,
+                    ref creator,
+                    ref origin,
+                    amount,
+                    ref fees,
+                    ref data,
+                } => {
+                    let fees = fees.to_fees().expect("Invalid asset fees in the dump");
+                    let info = AssetInfo::new(creator, origin, amount, fees, data);
+                    assets::Schema(&mut *fork).store(id, info);
+                }
+                Record::OpenOffers {
+                    ref asset_id,
+                    ref bids,
+                    ref asks,
+                } => {
+                    let open_offers = OpenOffers::new(offers(bids), offers(asks));
+                    offers::Schema(&mut *fork).store(asset_id, open_offers);
+                }
+                Record::OfferHistory {
+                    ref tx_hash,
+                    ref history,
+                } => {
+                    let history = history
+                        .iter()
+                        .map(|fill| HistoryOffer::new(&fill.tx_hash, fill.amount))
+                        .collect();
+                    history::Schema(&mut *fork).store(tx_hash, HistoryOffers::new(history));
+                }
+                Record::Status { tx_hash, code } => {
+                    let result = match code {
+                        0 => Ok(()),
+                        code => Err(Error::try_from(code).expect("Invalid status in the dump")),
+                    };
+                    status::Schema(&mut *fork).store(tx_hash, result);
+                }
+            }
+        }
+        supply::Schema(&mut *fork)
+            .mint(self.header.supply)
+            .expect("Supply of the dump overflows");
+    }
+}
@@ -32,7 +32,8 @@ impl<S> Schema<S>
 where
     S: AsRef<Snapshot>,
 {
-    fn index(self) -> MapIndex<S, Hash, ResultRepr> {
+    /// Internal `MapIndex` with immutable access.
+    pub fn index(self) -> MapIndex<S, Hash, ResultRepr> {
         let key = SERVICE_NAME.to_string() + ".statuses";
         MapIndex::new(key, self.0)
     }
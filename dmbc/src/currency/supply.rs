@@ -0,0 +1,188 @@
+//! Total coin supply.
+
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+
+use exonum::storage::{Entry, Fork, Snapshot};
+
+use currency::offers::{self, OpenOffers};
+use currency::wallet;
+use currency::SERVICE_NAME;
+
+/// Coins minted in the genesis wallet.
+pub const GENESIS_SUPPLY: u64 = 56_921_773_17197150;
+
+/// Breakdown of the coins present in the network.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Supply {
+    /// Coins ever minted.
+    pub total: u64,
+    /// Coins held by wallets.
+    pub wallets: u64,
+    /// Coins locked in open asks until they are matched.
+    pub escrowed: u64,
+}
+
+impl Supply {
+    /// Checks whether every minted coin is accounted for.
+    pub fn is_conserved(&self) -> bool {
+        self.wallets.checked_add(self.escrowed) == Some(self.total)
+    }
+
+    /// Minted coins that nothing holds, negative when more coins are held
+    /// than were minted.
+    pub fn discrepancy(&self) -> i64 {
+        let held = self.wallets.saturating_add(self.escrowed);
+        let max = i64::max_value() as u64;
+        if self.total >= held {
+            cmp::min(self.total - held, max) as i64
+        } else {
+            -(cmp::min(held - self.total, max) as i64)
+        }
+    }
+}
+
+/// An error type representing a supply that doesn't fit in `u64`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct OverflowError;
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl Error for OverflowError {
+    fn description(&self) -> &'static str {
+        "Total coin supply overflows."
+    }
+}
+
+/// Coins locked in the asks of `open_offers`, or `None` if they don't fit in
+/// `u64`.
+pub fn escrowed(open_offers: &OpenOffers) -> Option<u64> {
+    open_offers.asks().iter().fold(Some(0u64), |sum, asks| {
+        let amount = asks.offers()
+            .iter()
+            .fold(Some(0u64), |amount, ask| amount?.checked_add(ask.amount()));
+        sum?.checked_add(asks.price().checked_mul(amount?)?)
+    })
+}
+
+/// Schema for accessing the total coin supply.
+///
+/// Besides the coins ever minted, it keeps running totals of the coins held
+/// by wallets and escrowed in asks, updated whenever those are stored.
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// Internal `Entry` with immutable access.
+    pub fn entry(self) -> Entry<S, u64> {
+        let key = SERVICE_NAME.to_string() + ".supply";
+        Entry::new(key, self.0)
+    }
+
+    /// Checks whether the supply has been recorded. Chains started before
+    /// supply accounting was introduced don't have it.
+    pub fn exists(self) -> bool {
+        self.entry().exists()
+    }
+
+    /// Fetch the total coin supply.
+    pub fn fetch(self) -> u64 {
+        self.entry().get().unwrap_or(0)
+    }
+
+    /// Read the supply from the running totals, which is cheap enough to do
+    /// on every block.
+    pub fn totals(self) -> Supply {
+        let view = self.0;
+        Supply {
+            total: Schema(view.as_ref()).fetch(),
+            wallets: Entry::new(wallets_key(), view.as_ref()).get().unwrap_or(0),
+            escrowed: Entry::new(escrowed_key(), view.as_ref()).get().unwrap_or(0),
+        }
+    }
+
+    /// Walk wallets and open offers and count the coins they hold. Counts
+    /// that don't fit in `u64` are reported as `u64::max_value()`.
+    pub fn audit(self) -> Supply {
+        let view = self.0;
+
+        let wallets = wallet::Schema(view.as_ref())
+            .index()
+            .values()
+            .fold(Some(0u64), |sum, wallet| sum?.checked_add(wallet.balance()));
+
+        let escrowed = offers::Schema(view.as_ref())
+            .index()
+            .values()
+            .fold(Some(0u64), |sum, open_offers| sum?.checked_add(escrowed(&open_offers)?));
+
+        Supply {
+            total: Schema(view).fetch(),
+            wallets: wallets.unwrap_or_else(u64::max_value),
+            escrowed: escrowed.unwrap_or_else(u64::max_value),
+        }
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Internal `Entry` with mutable access.
+    pub fn entry_mut(&mut self) -> Entry<&mut Fork, u64> {
+        let key = SERVICE_NAME.to_string() + ".supply";
+        Entry::new(key, &mut *self.0)
+    }
+
+    /// Store the total coin supply.
+    pub fn store(&mut self, total: u64) {
+        self.entry_mut().set(total);
+    }
+
+    /// Account for a wallet balance going from `before` to `after`.
+    pub fn update_wallets(&mut self, before: u64, after: u64) {
+        update_total(Entry::new(wallets_key(), &mut *self.0), before, after);
+    }
+
+    /// Account for the coins escrowed in the asks of an asset going from
+    /// `before` to `after`.
+    pub fn update_escrowed(&mut self, before: u64, after: u64) {
+        update_total(Entry::new(escrowed_key(), &mut *self.0), before, after);
+    }
+
+    /// Increase the total coin supply by `amount`, unless that overflows.
+    pub fn mint(&mut self, amount: u64) -> Result<(), OverflowError> {
+        let total = Schema(&*self.0)
+            .fetch()
+            .checked_add(amount)
+            .ok_or(OverflowError)?;
+        self.store(total);
+        Ok(())
+    }
+}
+
+fn wallets_key() -> String {
+    SERVICE_NAME.to_string() + ".supply_wallets"
+}
+
+fn escrowed_key() -> String {
+    SERVICE_NAME.to_string() + ".supply_escrowed"
+}
+
+/// Replace `before` by `after` in a running total. Totals stop at the bounds
+/// of `u64`, which the audit reports as not conserved.
+fn update_total(mut entry: Entry<&mut Fork, u64>, before: u64, after: u64) {
+    let total = entry.get().unwrap_or(0);
+    let total = if after >= before {
+        total.saturating_add(after - before)
+    } else {
+        total.saturating_sub(before - after)
+    };
+    entry.set(total);
+}
@@ -5,14 +5,17 @@ use exonum::blockchain::Transaction;
 use exonum::crypto::PublicKey;
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets;
 use currency::assets::{AssetId, AssetInfo, MetaAsset};
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
-use currency::transactions::components::{FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -33,28 +36,33 @@ message!{
 }
 
 impl FeesCalculator for AddAssets {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let fees = ThirdPartyFees::new_add_assets(&view, self.meta_assets())?;
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
         if genesis_fees.recipient() != self.pub_key() {
-            fees_table.insert(*self.pub_key(), genesis_fees.add_assets());
+            items.push(FeeItem::new(
+                self.pub_key(),
+                genesis_fees.recipient(),
+                FeeReason::Blockchain,
+                genesis_fees.add_assets(),
+            ));
         }
 
         for (pub_key, fee) in fees.0 {
             if pub_key != *self.pub_key() {
-                *fees_table.entry(*self.pub_key()).or_insert(0) += fee;
+                items.push(FeeItem::new(self.pub_key(), &pub_key, FeeReason::Blockchain, fee));
             }
         }
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
 impl AddAssets {
 
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let tx_fee = genesis_fees.add_assets();
 
@@ -113,73 +121,39 @@ impl AddAssets {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_add_assets_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_add_assets_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_add_assets_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_add_assets_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_add_assets_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_add_assets_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for AddAssets {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
-
-        for asset in self.meta_assets() {
-            if !asset.verify() {
-                return false;
+        metrics::verify(TxType::AddAssets, || {
+            for asset in self.meta_assets() {
+                if !asset.verify() {
+                    return false;
+                }
             }
-        }
-
-        if cfg!(fuzzing) {
-            return true;
-        }
 
-        if !permissions::is_authorized(ADD_ASSETS_ID, vec![&self.pub_key()]) {
-            return false;
-        }
+            if cfg!(fuzzing) {
+                return true;
+            }
 
-        if !self.verify_signature(&self.pub_key()) {
-            return false;
-        }
+            if !permissions::is_authorized(ADD_ASSETS_ID, vec![&self.pub_key()]) {
+                return false;
+            }
 
-        VERIFY_SUCCESS_COUNT.inc();
+            if !self.verify_signature(&self.pub_key()) {
+                return false;
+            }
 
-        true
+            true
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
+        let timer = metrics::execute_started(TxType::AddAssets);
 
-        let result = self.process(view);
-
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::AddAssets, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
@@ -1,15 +1,16 @@
-use std::collections::HashMap;
-
 use exonum::blockchain::Transaction;
 use exonum::crypto::PublicKey;
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{IntCounter, Histogram};
 
 use currency::assets::TradeAsset;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::status;
-use currency::transactions::components::{FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::offers;
@@ -33,22 +34,28 @@ message! {
 }
 
 impl FeesCalculator for BidOffer {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let fees = ThirdPartyFees::new_trade(&*view, &[self.asset()])?;
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
         if genesis_fees.recipient() != self.pub_key() {
-            fees_table.insert(*self.pub_key(), genesis_fees.trade());
+            items.push(FeeItem::new(
+                self.pub_key(),
+                genesis_fees.recipient(),
+                FeeReason::Blockchain,
+                genesis_fees.trade(),
+            ));
         }
 
         for (pub_key, fee) in fees.0 {
             if pub_key != *self.pub_key() {
-                *fees_table.entry(*self.pub_key()).or_insert(0) += fee;
+                items.push(FeeItem::new(self.pub_key(), &pub_key, FeeReason::CreatorRoyalty, fee));
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -68,7 +75,7 @@ impl BidOffer {
         let mut bid = offers::create_bid(&mut wallet_from, &self.pub_key(),&self.asset(), &self.hash())?;
         let mut open_offers = offers::Schema(&mut *view).fetch(&self.asset().id());
 
-        let (update_wallets, history_offer)= offers::close_asks(&mut *view, &mut open_offers, &self.asset(), &mut bid, &mut wallet_from);
+        let (update_wallets, history_offer)= offers::close_asks(&mut *view, &mut open_offers, &self.asset(), &mut bid, &mut wallet_from, self.pub_key());
 
         if bid.amount() > 0 {
             open_offers.add_bid(self.asset().price(), bid.clone());
@@ -80,74 +87,41 @@ impl BidOffer {
         offers::Schema(&mut *view).store(&self.asset().id(), open_offers);
         wallet::Schema(&mut *view).store(self.pub_key(), wallet_from);
         offers::history::Schema(&mut *view).update(&self.hash(), &history_offer);
+        metrics::observe_matched(
+            TxType::BidOffer,
+            history_offer.iter().map(|offer| offer.amount()).sum(),
+        );
 
         Ok(())
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_bid_offer_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_bid_offer_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_bid_offer_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_bid_offer_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_bid_offer_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_bid_offer_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for BidOffer {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
-
-        if cfg!(fuzzing) {
-            return true;
-        }
+        metrics::verify(TxType::BidOffer, || {
+            if cfg!(fuzzing) {
+                return true;
+            }
 
-        if !permissions::is_authorized(BID_OFFER_ID, vec![&self.pub_key()]) {
-            return false;
-        }
+            if !permissions::is_authorized(BID_OFFER_ID, vec![&self.pub_key()]) {
+                return false;
+            }
 
-        let overflow = self.asset().price().checked_mul(self.asset().amount()).is_some();
-        let verify_ok = self.verify_signature(&self.pub_key());
+            let overflow = self.asset().price().checked_mul(self.asset().amount()).is_some();
+            let verify_ok = self.verify_signature(&self.pub_key());
 
-        if verify_ok && overflow {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            verify_ok && overflow
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
+        let timer = metrics::execute_started(TxType::BidOffer);
 
-        let result = self.process(view);
-
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::BidOffer, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
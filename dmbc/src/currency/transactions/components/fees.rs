@@ -259,6 +259,61 @@ impl ThirdPartyFees {
     }
 }
 
+/// Reason a fee is charged. Fee items are listed in the order of the
+/// variants.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeeReason {
+    /// Fee paid to the genesis wallet for processing the transaction.
+    Blockchain,
+    /// Fee paid to the creator of an asset involved in the transaction.
+    CreatorRoyalty,
+    /// Commission paid to the intermediary of the deal.
+    IntermediaryCommission,
+}
+
+/// A single fee payment made by a transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FeeItem {
+    pub payer: PublicKey,
+    pub recipient: PublicKey,
+    pub reason: FeeReason,
+    pub amount: u64,
+}
+
+impl FeeItem {
+    /// Create a new `FeeItem`.
+    pub fn new(payer: &PublicKey, recipient: &PublicKey, reason: FeeReason, amount: u64) -> Self {
+        FeeItem {
+            payer: *payer,
+            recipient: *recipient,
+            reason,
+            amount,
+        }
+    }
+}
+
+/// Order fee items by reason, then by recipient and payer, so that they
+/// don't depend on the order of the maps they are collected from.
+pub fn sort_fee_items(items: &mut [FeeItem]) {
+    items.sort_by_key(|item| (item.reason, item.recipient, item.payer));
+}
+
+/// Sum itemized fees per payer.
+pub fn fees_by_payer(items: &[FeeItem]) -> HashMap<PublicKey, u64> {
+    let mut fees_table = HashMap::new();
+    for item in items {
+        *fees_table.entry(item.payer).or_insert(0) += item.amount;
+    }
+    fees_table
+}
+
 pub trait FeesCalculator {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error>;
+    /// Every fee payment the transaction makes, with its recipient and reason,
+    /// in the order of `sort_fee_items`.
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error>;
+
+    /// Total fees charged to each payer.
+    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+        self.itemize_fees(view).map(|items| fees_by_payer(&items))
+    }
 }
@@ -0,0 +1,160 @@
+//! Transaction metrics.
+//!
+//! Every series is labeled with the transaction type, so a single vector
+//! covers all transactions. Executions are also labeled with the result,
+//! `ok` or the `snake_case` name of the `Error`.
+//!
+//! Transactions verified and executed inside `dry_run`, as by the simulation
+//! API, aren't counted.
+
+use std::cell::Cell;
+
+use prometheus::{self, HistogramTimer, HistogramVec, IntCounterVec};
+
+use currency::error::Error;
+use currency::events::TxType;
+
+lazy_static! {
+    static ref VERIFY: IntCounterVec = register_int_counter_vec!(
+        "dmbc_transaction_verify_total",
+        "Transaction verifications, by type and outcome.",
+        &["type", "result"]
+    ).unwrap();
+    static ref EXECUTE_STARTED: IntCounterVec = register_int_counter_vec!(
+        "dmbc_transaction_execute_started_total",
+        "Transaction executions started, by type.",
+        &["type"]
+    ).unwrap();
+    static ref EXECUTE: IntCounterVec = register_int_counter_vec!(
+        "dmbc_transaction_execute_total",
+        "Transaction executions finished without panicking, by type and result.",
+        &["type", "result"]
+    ).unwrap();
+    static ref EXECUTE_DURATION: HistogramVec = register_histogram_vec!(
+        "dmbc_transaction_execute_duration_seconds",
+        "Duration of transaction execution.",
+        &["type"]
+    ).unwrap();
+    static ref FEES: HistogramVec = register_histogram_vec!(
+        "dmbc_transaction_fees_coins",
+        "Fees paid by transactions, in coins.",
+        &["type"],
+        prometheus::exponential_buckets(1.0, 10.0, 12).unwrap()
+    ).unwrap();
+    static ref SIZE: HistogramVec = register_histogram_vec!(
+        "dmbc_transaction_size_bytes",
+        "Size of executed transactions.",
+        &["type"],
+        prometheus::exponential_buckets(64.0, 2.0, 12).unwrap()
+    ).unwrap();
+    static ref MATCHED: HistogramVec = register_histogram_vec!(
+        "dmbc_order_book_matched_amount",
+        "Asset amount matched against open offers by a single offer.",
+        &["type"],
+        prometheus::exponential_buckets(1.0, 10.0, 10).unwrap()
+    ).unwrap();
+}
+
+thread_local! {
+    static DRY_RUN: Cell<bool> = Cell::new(false);
+}
+
+/// Restores the previous dry run flag when dropped, also on panics.
+struct DryRunGuard(bool);
+
+impl Drop for DryRunGuard {
+    fn drop(&mut self) {
+        let previous = self.0;
+        DRY_RUN.with(|dry_run| dry_run.set(previous));
+    }
+}
+
+/// Run `f` without counting the transactions it verifies and executes.
+pub fn dry_run<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = DryRunGuard(DRY_RUN.with(|dry_run| dry_run.replace(true)));
+    f()
+}
+
+/// Checks whether the current thread is inside `dry_run`.
+pub fn is_dry_run() -> bool {
+    DRY_RUN.with(|dry_run| dry_run.get())
+}
+
+/// Times an execution, unless it is a dry run.
+pub struct ExecuteTimer(Option<HistogramTimer>);
+
+impl ExecuteTimer {
+    /// Record the time elapsed since the execution started.
+    pub fn observe_duration(self) {
+        if let Some(timer) = self.0 {
+            timer.observe_duration();
+        }
+    }
+}
+
+fn result_label(result: &Result<(), Error>) -> &'static str {
+    match *result {
+        Ok(()) => "ok",
+        Err(ref e) => e.name(),
+    }
+}
+
+/// Run the verification of a transaction and count its outcome.
+pub fn verify<F>(tx_type: TxType, verify: F) -> bool
+where
+    F: FnOnce() -> bool,
+{
+    let ok = verify();
+    if !is_dry_run() {
+        let result = if ok { "ok" } else { "failed" };
+        VERIFY.with_label_values(&[tx_type.name(), result]).inc();
+    }
+    ok
+}
+
+/// Count the start of an execution and time it until the returned timer
+/// is observed.
+pub fn execute_started(tx_type: TxType) -> ExecuteTimer {
+    if is_dry_run() {
+        return ExecuteTimer(None);
+    }
+    EXECUTE_STARTED.with_label_values(&[tx_type.name()]).inc();
+    let timer = EXECUTE_DURATION
+        .with_label_values(&[tx_type.name()])
+        .start_timer();
+    ExecuteTimer(Some(timer))
+}
+
+/// Count a finished execution by its result.
+pub fn executed(tx_type: TxType, result: &Result<(), Error>) {
+    if is_dry_run() {
+        return;
+    }
+    EXECUTE
+        .with_label_values(&[tx_type.name(), result_label(result)])
+        .inc();
+}
+
+/// Record the size of an executed transaction.
+pub fn observe_size(tx_type: TxType, bytes: usize) {
+    if !is_dry_run() {
+        SIZE.with_label_values(&[tx_type.name()]).observe(bytes as f64);
+    }
+}
+
+/// Record the fees a transaction paid, which a failed one may have too.
+pub fn observe_fees(tx_type: TxType, coins: u64) {
+    if !is_dry_run() {
+        FEES.with_label_values(&[tx_type.name()]).observe(coins as f64);
+    }
+}
+
+/// Record the asset amount an offer matched in the order book.
+pub fn observe_matched(tx_type: TxType, amount: u64) {
+    if !is_dry_run() {
+        MATCHED.with_label_values(&[tx_type.name()]).observe(amount as f64);
+    }
+}
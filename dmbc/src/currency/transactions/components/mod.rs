@@ -2,9 +2,13 @@
 
 mod fees;
 mod intermediary;
+pub mod metrics;
 pub mod permissions;
 
-pub use currency::transactions::components::fees::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+pub use currency::transactions::components::fees::{
+    fees_by_payer, sort_fee_items, FeeItem, FeeReason, FeeStrategy, FeesCalculator,
+    ThirdPartyFees,
+};
 pub use currency::transactions::components::intermediary::Intermediary;
 // pub use currency::transactions::components::permissions::{mask_for, has_permission, is_authorized};
 // pub use currency::transactions::components::permissions::{
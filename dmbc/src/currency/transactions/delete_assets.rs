@@ -4,14 +4,15 @@ use exonum::blockchain::Transaction;
 use exonum::crypto::PublicKey;
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets;
 use currency::assets::AssetBundle;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
-use currency::transactions::components::FeesCalculator;
+use currency::transactions::components::{sort_fee_items, FeeItem, FeeReason, FeesCalculator};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -32,22 +33,26 @@ message! {
 }
 
 impl FeesCalculator for DeleteAssets {
-    fn calculate_fees(&self, _view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, _view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let tx_fee = genesis_fees.delete_assets();
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
         if genesis_fees.recipient() != self.pub_key() {
-            fees_table.insert(*self.pub_key(), tx_fee);
+            items.push(FeeItem::new(
+                self.pub_key(),
+                genesis_fees.recipient(),
+                FeeReason::Blockchain,
+                tx_fee,
+            ));
         }
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
 impl DeleteAssets {
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
-
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
 
         let genesis_pub = genesis_fees.recipient();
@@ -85,66 +90,29 @@ impl DeleteAssets {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_delete_assets_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_delete_assets_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_delete_assets_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_delete_assets_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_delete_assets_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_delete_assets_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for DeleteAssets {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
+        metrics::verify(TxType::DeleteAssets, || {
+            if cfg!(fuzzing) {
+                return true;
+            }
 
-        if cfg!(fuzzing) {
-            return true;
-        }
+            if !permissions::is_authorized(DELETE_ASSETS_ID, vec![self.pub_key()]) {
+                return false;
+            }
 
-        if !permissions::is_authorized(DELETE_ASSETS_ID, vec![self.pub_key()]) {
-            return false;
-        }
-
-        if self.verify_signature(self.pub_key()) {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            self.verify_signature(self.pub_key())
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
+        let timer = metrics::execute_started(TxType::DeleteAssets);
 
-        let result = self.process(view);
-
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::DeleteAssets, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
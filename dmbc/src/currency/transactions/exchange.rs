@@ -1,17 +1,18 @@
-use std::collections::HashMap;
-
 use exonum::blockchain::Transaction;
 use exonum::crypto;
 use exonum::crypto::{PublicKey, Signature};
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets::AssetBundle;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeeStrategy, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -46,7 +47,7 @@ message! {
 }
 
 impl FeesCalculator for Exchange {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let offer = self.offer();
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let fees = ThirdPartyFees::new_exchange(
@@ -59,12 +60,17 @@ impl FeesCalculator for Exchange {
         let fee_strategy =
             FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
 
         let payers = self.payers(&fee_strategy, genesis_fees.exchange())?;
         for (payer_key, fee) in payers {
             if genesis_fees.recipient() != &payer_key {
-                fees_table.insert(payer_key, fee);
+                items.push(FeeItem::new(
+                    &payer_key,
+                    genesis_fees.recipient(),
+                    FeeReason::Blockchain,
+                    fee,
+                ));
             }
         }
 
@@ -73,12 +79,18 @@ impl FeesCalculator for Exchange {
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    items.push(FeeItem::new(
+                        &payer_key,
+                        &receiver_key,
+                        FeeReason::CreatorRoyalty,
+                        fee,
+                    ));
                 }
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -102,8 +114,6 @@ impl Exchange {
     }
 
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
-
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
 
         let offer = self.offer();
@@ -189,80 +199,43 @@ impl Exchange {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_exchange_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for Exchange {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
+        metrics::verify(TxType::Exchange, || {
+            let offer = self.offer();
 
-        let offer = self.offer();
-
-        let wallets_ok = offer.sender() != offer.recipient();
-        let fee_strategy_ok = match FeeStrategy::try_from(offer.fee_strategy()).unwrap() {
-            FeeStrategy::Recipient | FeeStrategy::Sender | FeeStrategy::RecipientAndSender => true,
-            _ => false,
-        };
+            let wallets_ok = offer.sender() != offer.recipient();
+            let fee_strategy_ok = match FeeStrategy::try_from(offer.fee_strategy()).unwrap() {
+                FeeStrategy::Recipient | FeeStrategy::Sender | FeeStrategy::RecipientAndSender => true,
+                _ => false,
+            };
 
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
+            if cfg!(fuzzing) {
+                return wallets_ok && fee_strategy_ok;
+            }
 
-        if !permissions::is_authorized(EXCHANGE_ID, vec![
-            &self.offer().sender(), 
-            &self.offer().recipient()
-        ]) {
-            return false;
-        }
+            if !permissions::is_authorized(EXCHANGE_ID, vec![
+                &self.offer().sender(), 
+                &self.offer().recipient()
+            ]) {
+                return false;
+            }
 
-        let recipient_ok = self.verify_signature(offer.recipient());
-        let sender_ok = crypto::verify(self.sender_signature(), &offer.raw, offer.sender());
+            let recipient_ok = self.verify_signature(offer.recipient());
+            let sender_ok = crypto::verify(self.sender_signature(), &offer.raw, offer.sender());
 
-        if wallets_ok && fee_strategy_ok && recipient_ok && sender_ok {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            wallets_ok && fee_strategy_ok && recipient_ok && sender_ok
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
-
-        let result = self.process(view);
+        let timer = metrics::execute_started(TxType::Exchange);
 
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::Exchange, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
@@ -1,19 +1,18 @@
-use std::collections::HashMap;
-
 use exonum::blockchain::Transaction;
 use exonum::crypto;
 use exonum::crypto::{PublicKey, Signature};
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets::AssetBundle;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
 use currency::transactions::components::{
-    FeeStrategy, FeesCalculator, Intermediary, ThirdPartyFees,
+    sort_fee_items, FeeItem, FeeReason, FeeStrategy, FeesCalculator, Intermediary, ThirdPartyFees,
 };
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::{Service, SERVICE_ID};
@@ -51,43 +50,57 @@ message! {
 }
 
 impl FeesCalculator for ExchangeIntermediary {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let offer = self.offer();
         let genesis_fee = CONFIGURATION.read().unwrap().fees().exchange();
-        let mut fees = ThirdPartyFees::new_exchange(
+        let genesis_wallet = Service::genesis_wallet(&*view);
+        let fees = ThirdPartyFees::new_exchange(
             &*view,
             offer
                 .sender_assets()
                 .into_iter()
                 .chain(offer.recipient_assets().into_iter()),
         )?;
-        fees.add_fee(
-            offer.intermediary().wallet(),
-            offer.intermediary().commission(),
-        );
         let fee_strategy =
             FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
 
         let payers = self.payers(&fee_strategy, genesis_fee)?;
         for (payer_key, fee) in payers {
-            if Service::genesis_wallet(&*view) != payer_key {
-                fees_table.insert(payer_key, fee);
+            if genesis_wallet != payer_key {
+                items.push(FeeItem::new(
+                    &payer_key,
+                    &genesis_wallet,
+                    FeeReason::Blockchain,
+                    fee,
+                ));
             }
         }
 
-        for (receiver_key, fee) in fees.0 {
+        let intermediary = offer.intermediary();
+        let third_party = fees
+            .0
+            .into_iter()
+            .map(|(key, fee)| (key, fee, FeeReason::CreatorRoyalty))
+            .chain(Some((
+                *intermediary.wallet(),
+                intermediary.commission(),
+                FeeReason::IntermediaryCommission,
+            )));
+
+        for (receiver_key, fee, reason) in third_party {
             let payers = self.payers(&fee_strategy, fee)?;
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    items.push(FeeItem::new(&payer_key, &receiver_key, reason, fee));
                 }
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -111,8 +124,6 @@ impl ExchangeIntermediary {
     }
 
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
-
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
 
         let offer = self.offer();
@@ -210,85 +221,48 @@ impl ExchangeIntermediary {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_intermediary_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_intermediary_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_intermediary_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_intermediary_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_exchange_intermediary_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_exchange_intermediary_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for ExchangeIntermediary {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
-
-        let offer = self.offer();
+        metrics::verify(TxType::ExchangeIntermediary, || {
+            let offer = self.offer();
 
-        let wallets_ok = offer.sender() != offer.recipient()
-            && offer.intermediary().wallet() != offer.sender()
-            && offer.intermediary().wallet() != offer.recipient();
-        let fee_strategy_ok = FeeStrategy::try_from(offer.fee_strategy()).is_some();
+            let wallets_ok = offer.sender() != offer.recipient()
+                && offer.intermediary().wallet() != offer.sender()
+                && offer.intermediary().wallet() != offer.recipient();
+            let fee_strategy_ok = FeeStrategy::try_from(offer.fee_strategy()).is_some();
 
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
+            if cfg!(fuzzing) {
+                return wallets_ok && fee_strategy_ok;
+            }
 
-        if !permissions::is_authorized(EXCHANGE_INTERMEDIARY_ID, vec![
-            &self.offer().sender(), 
-            &self.offer().recipient(), 
-            &self.offer().intermediary().wallet()
-        ]) {
-            return false;
-        }
+            if !permissions::is_authorized(EXCHANGE_INTERMEDIARY_ID, vec![
+                &self.offer().sender(), 
+                &self.offer().recipient(), 
+                &self.offer().intermediary().wallet()
+            ]) {
+                return false;
+            }
 
-        let recipient_ok = self.verify_signature(offer.recipient());
-        let sender_ok = crypto::verify(self.sender_signature(), &offer.raw, offer.sender());
-        let intermediary_ok = crypto::verify(
-            self.intermediary_signature(),
-            &offer.raw,
-            offer.intermediary().wallet(),
-        );
+            let recipient_ok = self.verify_signature(offer.recipient());
+            let sender_ok = crypto::verify(self.sender_signature(), &offer.raw, offer.sender());
+            let intermediary_ok = crypto::verify(
+                self.intermediary_signature(),
+                &offer.raw,
+                offer.intermediary().wallet(),
+            );
 
-        if wallets_ok && fee_strategy_ok && recipient_ok && sender_ok && intermediary_ok {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            wallets_ok && fee_strategy_ok && recipient_ok && sender_ok && intermediary_ok
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
+        let timer = metrics::execute_started(TxType::ExchangeIntermediary);
 
-        let result = self.process(view);
-
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::ExchangeIntermediary, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
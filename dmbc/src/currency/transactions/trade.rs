@@ -5,13 +5,16 @@ use exonum::crypto;
 use exonum::crypto::{PublicKey, Signature};
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets::TradeAsset;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeeStrategy, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -43,19 +46,24 @@ message! {
 }
 
 impl FeesCalculator for Trade {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let offer = self.offer();
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
         let fee_strategy =
             FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
 
         let payers = self.payers(&fee_strategy, genesis_fees.trade())?;
         for (payer_key, fee) in payers {
             if genesis_fees.recipient() != &payer_key {
-                fees_table.insert(payer_key, fee);
+                items.push(FeeItem::new(
+                    &payer_key,
+                    genesis_fees.recipient(),
+                    FeeReason::Blockchain,
+                    fee,
+                ));
             }
         }
 
@@ -64,12 +72,18 @@ impl FeesCalculator for Trade {
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    items.push(FeeItem::new(
+                        &payer_key,
+                        &receiver_key,
+                        FeeReason::CreatorRoyalty,
+                        fee,
+                    ));
                 }
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -109,8 +123,6 @@ impl Trade {
     }
 
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
-
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
 
         let offer = self.offer();
@@ -211,92 +223,55 @@ impl Trade {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_trade_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for Trade {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
-
-        let wallets_ok = self.offer().buyer() != self.offer().seller();
-        let fee_strategy_ok = match FeeStrategy::try_from(self.offer().fee_strategy()).unwrap() {
-            FeeStrategy::Recipient | FeeStrategy::Sender | FeeStrategy::RecipientAndSender => true,
-            _ => false,
-        };
-
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
-
-        if !permissions::is_authorized(TRADE_ID, vec![
-            &self.offer().seller(),
-            &self.offer().buyer()
-        ]) {
-            return false;
-        }
-
-        let seller_verify_ok = crypto::verify(
-            self.seller_signature(),
-            &self.offer().raw,
-            self.offer().seller(),
-        );
-        let buyer_verify_ok = self.verify_signature(&self.offer().buyer());
+        metrics::verify(TxType::Trade, || {
+            let wallets_ok = self.offer().buyer() != self.offer().seller();
+            let fee_strategy_ok = match FeeStrategy::try_from(self.offer().fee_strategy()).unwrap() {
+                FeeStrategy::Recipient | FeeStrategy::Sender | FeeStrategy::RecipientAndSender => true,
+                _ => false,
+            };
+
+            if cfg!(fuzzing) {
+                return wallets_ok && fee_strategy_ok;
+            }
 
-        let price_verify_ok = self.offer()
-            .assets()
-            .iter()
-            .map(|asset| asset.amount().overflowing_mul(asset.price()))
-            .try_fold(0u64, |value, (price, overflown)| match overflown { 
-                true => None,
-                _ => value.checked_add(price),
-            })
-            .is_some();
+            if !permissions::is_authorized(TRADE_ID, vec![
+                &self.offer().seller(),
+                &self.offer().buyer()
+            ]) {
+                return false;
+            }
 
-        if wallets_ok && fee_strategy_ok && buyer_verify_ok && seller_verify_ok && price_verify_ok {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            let seller_verify_ok = crypto::verify(
+                self.seller_signature(),
+                &self.offer().raw,
+                self.offer().seller(),
+            );
+            let buyer_verify_ok = self.verify_signature(&self.offer().buyer());
+
+            let price_verify_ok = self.offer()
+                .assets()
+                .iter()
+                .map(|asset| asset.amount().overflowing_mul(asset.price()))
+                .try_fold(0u64, |value, (price, overflown)| match overflown { 
+                    true => None,
+                    _ => value.checked_add(price),
+                })
+                .is_some();
+
+            wallets_ok && fee_strategy_ok && buyer_verify_ok && seller_verify_ok && price_verify_ok
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
+        let timer = metrics::execute_started(TxType::Trade);
 
-        let result = self.process(view);
-
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::Trade, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
@@ -1,18 +1,19 @@
-use std::collections::HashMap;
-
 use exonum::blockchain::Transaction;
 use exonum::crypto;
 use exonum::crypto::{PublicKey, Signature};
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets::TradeAsset;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
 use currency::transactions::components::Intermediary;
-use currency::transactions::components::{FeeStrategy, FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeeStrategy, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -46,37 +47,50 @@ message! {
 }
 
 impl FeesCalculator for TradeIntermediary {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let offer = self.offer();
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
-        let mut fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
-        fees.add_fee(
-            offer.intermediary().wallet(),
-            offer.intermediary().commission(),
-        );
+        let fees = ThirdPartyFees::new_trade(&*view, &offer.assets())?;
         let fee_strategy =
             FeeStrategy::try_from(offer.fee_strategy()).expect("fee strategy must be valid");
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
 
         let payers = self.payers(&fee_strategy, genesis_fees.trade())?;
         for (payer_key, fee) in payers {
             if genesis_fees.recipient() != &payer_key {
-                fees_table.insert(payer_key, fee);
+                items.push(FeeItem::new(
+                    &payer_key,
+                    genesis_fees.recipient(),
+                    FeeReason::Blockchain,
+                    fee,
+                ));
             }
         }
 
-        for (receiver_key, fee) in fees.0 {
+        let intermediary = offer.intermediary();
+        let third_party = fees
+            .0
+            .into_iter()
+            .map(|(key, fee)| (key, fee, FeeReason::CreatorRoyalty))
+            .chain(Some((
+                *intermediary.wallet(),
+                intermediary.commission(),
+                FeeReason::IntermediaryCommission,
+            )));
+
+        for (receiver_key, fee, reason) in third_party {
             let payers = self.payers(&fee_strategy, fee)?;
 
             for (payer_key, fee) in payers {
                 if payer_key != receiver_key {
-                    *fees_table.entry(payer_key).or_insert(0) += fee;
+                    items.push(FeeItem::new(&payer_key, &receiver_key, reason, fee));
                 }
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -116,8 +130,6 @@ impl TradeIntermediary {
     }
 
     fn process(&self, view: &mut Fork) -> Result<(), Error> {
-        info!("Processing tx: {:?}", self);
-
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
 
         let offer = self.offer();
@@ -230,96 +242,59 @@ impl TradeIntermediary {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_intermediary_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_intermediary_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_intermediary_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_intermediary_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_trade_intermediary_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_trade_intermediary_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for TradeIntermediary {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
+        metrics::verify(TxType::TradeIntermediary, || {
+            let offer = self.offer();
 
-        let offer = self.offer();
-
-        let wallets_ok = offer.seller() != offer.buyer()
-            && offer.intermediary().wallet() != offer.seller()
-            && offer.intermediary().wallet() != offer.buyer();
-        let fee_strategy_ok = FeeStrategy::try_from(offer.fee_strategy()).is_some();
-
-        if cfg!(fuzzing) {
-            return wallets_ok && fee_strategy_ok;
-        }
+            let wallets_ok = offer.seller() != offer.buyer()
+                && offer.intermediary().wallet() != offer.seller()
+                && offer.intermediary().wallet() != offer.buyer();
+            let fee_strategy_ok = FeeStrategy::try_from(offer.fee_strategy()).is_some();
 
-        if !permissions::is_authorized(TRADE_INTERMEDIARY_ID, vec![
-            &self.offer().seller(),
-            &self.offer().buyer(),
-            &self.offer().intermediary().wallet()
-        ]) {
-            return false;
-        }
-
-        let buyer_ok = self.verify_signature(offer.buyer());
-
-        let seller_ok = crypto::verify(self.seller_signature(), &offer.raw, offer.seller());
-        let intermediary_ok = crypto::verify(
-            self.intermediary_signature(),
-            &offer.raw,
-            offer.intermediary().wallet(),
-        );
+            if cfg!(fuzzing) {
+                return wallets_ok && fee_strategy_ok;
+            }
 
-        let price_ok = self.offer()
-            .assets()
-            .iter()
-            .map(|asset| asset.amount().overflowing_mul(asset.price()))
-            .try_fold(0u64, |value, (price, overflown)| match overflown { 
-                true => None,
-                _ => value.checked_add(price),
-            })
-            .is_some();
+            if !permissions::is_authorized(TRADE_INTERMEDIARY_ID, vec![
+                &self.offer().seller(),
+                &self.offer().buyer(),
+                &self.offer().intermediary().wallet()
+            ]) {
+                return false;
+            }
 
-        if wallets_ok && fee_strategy_ok && buyer_ok && seller_ok && intermediary_ok && price_ok {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            let buyer_ok = self.verify_signature(offer.buyer());
+
+            let seller_ok = crypto::verify(self.seller_signature(), &offer.raw, offer.seller());
+            let intermediary_ok = crypto::verify(
+                self.intermediary_signature(),
+                &offer.raw,
+                offer.intermediary().wallet(),
+            );
+
+            let price_ok = self.offer()
+                .assets()
+                .iter()
+                .map(|asset| asset.amount().overflowing_mul(asset.price()))
+                .try_fold(0u64, |value, (price, overflown)| match overflown { 
+                    true => None,
+                    _ => value.checked_add(price),
+                })
+                .is_some();
+
+            wallets_ok && fee_strategy_ok && buyer_ok && seller_ok && intermediary_ok && price_ok
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
-
-        let result = self.process(view);
+        let timer = metrics::execute_started(TxType::TradeIntermediary);
 
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::TradeIntermediary, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
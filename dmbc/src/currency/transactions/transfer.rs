@@ -1,16 +1,17 @@
-use std::collections::HashMap;
-
 use exonum::blockchain::Transaction;
 use exonum::crypto::PublicKey;
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{Histogram, IntCounter};
 
 use currency::assets::AssetBundle;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::service::CONFIGURATION;
 use currency::status;
-use currency::transactions::components::{FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -34,22 +35,28 @@ message! {
 }
 
 impl FeesCalculator for Transfer {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
         let fees = ThirdPartyFees::new_transfer(&*view, self.assets())?;
 
-        let mut fees_table = HashMap::new();
+        let mut items = Vec::new();
         if genesis_fees.recipient() != self.from() {
-            fees_table.insert(*self.from(), genesis_fees.transfer());
+            items.push(FeeItem::new(
+                self.from(),
+                genesis_fees.recipient(),
+                FeeReason::Blockchain,
+                genesis_fees.transfer(),
+            ));
         }
 
         for (pub_key, fee) in fees.0 {
             if pub_key != *self.from() {
-                *fees_table.entry(*self.from()).or_insert(0) += fee;
+                items.push(FeeItem::new(self.from(), &pub_key, FeeReason::CreatorRoyalty, fee));
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -99,73 +106,36 @@ impl Transfer {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_transfer_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for Transfer {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
+        metrics::verify(TxType::Transfer, || {
+            let wallets_ok = self.from() != self.to();
 
-        let wallets_ok = self.from() != self.to();
-
-        if cfg!(fuzzing) {
-            return wallets_ok;
-        }
+            if cfg!(fuzzing) {
+                return wallets_ok;
+            }
 
-        if !permissions::is_authorized(TRANSFER_ID, vec![
-            &self.from(),
-            &self.to()
-        ]) {
-            return false;
-        }
+            if !permissions::is_authorized(TRANSFER_ID, vec![
+                &self.from(),
+                &self.to()
+            ]) {
+                return false;
+            }
 
-        let verify_ok = self.verify_signature(&self.from());
+            let verify_ok = self.verify_signature(&self.from());
 
-        if wallets_ok && verify_ok {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            wallets_ok && verify_ok
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
+        let timer = metrics::execute_started(TxType::Transfer);
 
-        let result = self.process(view);
-
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::Transfer, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
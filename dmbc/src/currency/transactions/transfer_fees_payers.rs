@@ -1,15 +1,16 @@
-use std::collections::HashMap;
-
 use exonum::blockchain::Transaction;
 use exonum::crypto::{PublicKey, Signature};
 use exonum::messages::Message;
 use exonum::storage::Fork;
-use prometheus::{IntCounter, Histogram};
 
 use currency::assets::AssetBundle;
 use currency::error::Error;
+use currency::events::{self, TxType};
 use currency::status;
-use currency::transactions::components::{FeesCalculator, ThirdPartyFees};
+use currency::transactions::components::{
+    sort_fee_items, FeeItem, FeeReason, FeesCalculator, ThirdPartyFees,
+};
+use currency::transactions::components::metrics;
 use currency::transactions::components::permissions;
 use currency::wallet;
 use currency::SERVICE_ID;
@@ -44,22 +45,30 @@ message! {
 }
 
 impl FeesCalculator for TransferWithFeesPayer {
-    fn calculate_fees(&self, view: &mut Fork) -> Result<HashMap<PublicKey, u64>, Error> {
+    fn itemize_fees(&self, view: &mut Fork) -> Result<Vec<FeeItem>, Error> {
         let genesis_fees = CONFIGURATION.read().unwrap().fees();
-        let fees = ThirdPartyFees::new_transfer(&*view, self.offer().assets())?;
-
-        let mut fees_table = HashMap::new();
-        if genesis_fees.recipient() != self.offer().fees_payer() {
-            fees_table.insert(*self.offer().fees_payer(), genesis_fees.transfer());
+        let offer = self.offer();
+        let fees = ThirdPartyFees::new_transfer(&*view, offer.assets())?;
+        let fees_payer = offer.fees_payer();
+
+        let mut items = Vec::new();
+        if genesis_fees.recipient() != fees_payer {
+            items.push(FeeItem::new(
+                fees_payer,
+                genesis_fees.recipient(),
+                FeeReason::Blockchain,
+                genesis_fees.transfer(),
+            ));
         }
 
         for (pub_key, fee) in fees.0 {
-            if pub_key != *self.offer().fees_payer() {
-                *fees_table.entry(*self.offer().fees_payer()).or_insert(0) += fee;
+            if pub_key != *fees_payer {
+                items.push(FeeItem::new(fees_payer, &pub_key, FeeReason::CreatorRoyalty, fee));
             }
         }
 
-        Ok(fees_table)
+        sort_fee_items(&mut items);
+        Ok(items)
     }
 }
 
@@ -114,75 +123,38 @@ impl TransferWithFeesPayer {
     }
 }
 
-lazy_static! {
-    static ref VERIFY_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_fees_payer_verify_count",
-        "Times .verify() was called on a transaction."
-    ).unwrap();
-    static ref VERIFY_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_fees_payer_verify_success_count",
-        "Times verification was successfull on a transaction."
-    ).unwrap();
-    static ref EXECUTE_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_fees_payer_execute_count",
-        "Transactions executed."
-    ).unwrap();
-    static ref EXECUTE_SUCCESS_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_fees_payer_execute_success_count",
-        "Times transaction execution reported a success."
-    ).unwrap();
-    static ref EXECUTE_FINISH_COUNT: IntCounter = register_int_counter!(
-        "dmbc_transaction_transfer_fees_payer_execute_finish_count",
-        "Times transaction has finished executing without panicking."
-    ).unwrap();
-    static ref EXECUTE_DURATION: Histogram = register_histogram!(
-        "dmbc_transaction_transfer_fees_payer_execute_duration_seconds",
-        "Duration of transaction execution."
-    ).unwrap();
-}
-
 impl Transaction for TransferWithFeesPayer {
     fn verify(&self) -> bool {
-        VERIFY_COUNT.inc();
-
-        let wallets_ok = (self.offer().from() != self.offer().to())
-            && (self.offer().from() != self.offer().fees_payer());
+        metrics::verify(TxType::TransferWithFeesPayer, || {
+            let wallets_ok = (self.offer().from() != self.offer().to())
+                && (self.offer().from() != self.offer().fees_payer());
 
-        if cfg!(fuzzing) {
-            return wallets_ok;
-        }
+            if cfg!(fuzzing) {
+                return wallets_ok;
+            }
 
-        if !permissions::is_authorized(TRANSFER_FEES_PAYER_ID, vec![
-            &self.offer().from(),
-            &self.offer().to(),
-            &self.offer().fees_payer()
-        ]) {
-            return false;
-        }
+            if !permissions::is_authorized(TRANSFER_FEES_PAYER_ID, vec![
+                &self.offer().from(),
+                &self.offer().to(),
+                &self.offer().fees_payer()
+            ]) {
+                return false;
+            }
 
-        let verify_ok = self.verify_signature(&self.offer().from());
+            let verify_ok = self.verify_signature(&self.offer().from());
 
-        if wallets_ok && verify_ok {
-            VERIFY_SUCCESS_COUNT.inc();
-            true
-        } else {
-            false
-        }
+            wallets_ok && verify_ok
+        })
     }
 
     fn execute(&self, view: &mut Fork) {
-        EXECUTE_COUNT.inc();
-        let timer = EXECUTE_DURATION.start_timer();
-
-        let result = self.process(view);
+        let timer = metrics::execute_started(TxType::TransferWithFeesPayer);
 
-        if let &Ok(_) = &result {
-            EXECUTE_SUCCESS_COUNT.inc();
-        }
+        let result = events::capture(view, self, |view| self.process(view));
+        metrics::executed(TxType::TransferWithFeesPayer, &result);
 
         status::Schema(view).store(self.hash(), result);
 
         timer.observe_duration();
-        EXECUTE_FINISH_COUNT.inc();
     }
 }
@@ -0,0 +1,111 @@
+//! Past states of wallets.
+//!
+//! Whenever a wallet is stored, its new state is recorded under the height
+//! of the block being built, replacing a state recorded earlier in the same
+//! block. For every wallet the heights with a recorded state are listed in
+//! ascending order, so the state at the end of any block is found with a
+//! binary search instead of replaying blocks.
+//!
+//! Nodes that existed before wallet history was introduced start recording
+//! at the block of the first change after the upgrade; earlier states are
+//! unknown.
+
+use exonum::blockchain;
+use exonum::crypto::PublicKey;
+use exonum::storage::{Entry, Fork, ListIndex, MapIndex, Snapshot};
+
+use currency::wallet;
+use currency::wallet::Wallet;
+use currency::SERVICE_NAME;
+
+/// The schema for accessing past wallet states.
+pub struct Schema<S>(pub S)
+where
+    S: AsRef<Snapshot>;
+
+impl<S> Schema<S>
+where
+    S: AsRef<Snapshot>,
+{
+    /// States of a wallet by the height of the block that produced them.
+    pub fn versions(self, pub_key: &PublicKey) -> MapIndex<S, u64, Wallet> {
+        let key = SERVICE_NAME.to_string() + ".wallet_versions";
+        MapIndex::with_prefix(key, pub_key.as_ref().to_vec(), self.0)
+    }
+
+    /// Heights of the blocks that changed a wallet, in ascending order.
+    pub fn heights(self, pub_key: &PublicKey) -> ListIndex<S, u64> {
+        let key = SERVICE_NAME.to_string() + ".wallet_heights";
+        ListIndex::with_prefix(key, pub_key.as_ref().to_vec(), self.0)
+    }
+
+    /// Height from which wallet states are known.
+    pub fn since(self) -> Entry<S, u64> {
+        let key = SERVICE_NAME.to_string() + ".wallet_history_since";
+        Entry::new(key, self.0)
+    }
+
+    /// Fetch the state of a wallet at the end of the block at `height`, or
+    /// `None` if it is older than the recorded history.
+    pub fn fetch(self, pub_key: &PublicKey, height: u64) -> Option<Wallet> {
+        let view = self.0.as_ref();
+        match Schema(view).since().get() {
+            Some(since) if since <= height => (),
+            _ => return None,
+        }
+
+        let heights = Schema(view).heights(pub_key);
+        if heights.len() == 0 {
+            // Not changed since the history started.
+            return Some(wallet::Schema(view).fetch(pub_key));
+        }
+
+        // Find the number of recorded heights not above `height`.
+        let (mut low, mut high) = (0, heights.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if heights.get(middle).unwrap() <= height {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        match low {
+            0 => Some(Wallet::new_empty()),
+            n => {
+                let version = heights.get(n - 1).unwrap();
+                Schema(view).versions(pub_key).get(&version)
+            }
+        }
+    }
+}
+
+impl<'a> Schema<&'a mut Fork> {
+    /// Record that a wallet changed from `previous` to `wallet` in the block
+    /// being built.
+    pub fn record(&mut self, pub_key: &PublicKey, previous: Wallet, wallet: Wallet) {
+        let height = blockchain::Schema::new(&*self.0)
+            .block_hashes_by_height()
+            .len();
+        let since = match Schema(&*self.0).since().get() {
+            Some(since) => since,
+            None => {
+                let since = height.saturating_sub(1);
+                Schema(&mut *self.0).since().set(since);
+                since
+            }
+        };
+
+        let last = Schema(&*self.0).heights(pub_key).last();
+        if last != Some(height) {
+            let is_empty = previous.balance() == 0 && previous.assets().is_empty();
+            if last.is_none() && height > since && !is_empty {
+                // The state before the first recorded change.
+                Schema(&mut *self.0).versions(pub_key).put(&since, previous);
+                Schema(&mut *self.0).heights(pub_key).push(since);
+            }
+            Schema(&mut *self.0).heights(pub_key).push(height);
+        }
+        Schema(&mut *self.0).versions(pub_key).put(&height, wallet);
+    }
+}
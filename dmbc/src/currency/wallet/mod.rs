@@ -1,5 +1,6 @@
 //! Types and operations on wallets in the blockchain network.
 
+pub mod history;
 mod schema;
 mod wallet;
 
@@ -1,6 +1,9 @@
 use exonum::crypto::PublicKey;
 use exonum::storage::{Fork, MapIndex, Snapshot};
 
+use currency::events;
+use currency::supply;
+use currency::wallet::history;
 use currency::wallet::Wallet;
 use currency::SERVICE_NAME;
 
@@ -34,16 +37,29 @@ impl<'a> Schema<&'a mut Fork> {
         MapIndex::new(key, &mut *self.0)
     }
 
-    /// Store the new state for a wallet in the database.
+    /// Store the new state for a wallet in the database, recording it in
+    /// the wallet history and the coin supply.
     pub fn store(&mut self, pub_key: &PublicKey, wallet: Wallet) {
         match (wallet.balance(), wallet.assets().len()) {
             (0, 0) => self.remove(pub_key),
-            (_, _) => self.index_mut().put(pub_key, wallet),
+            (_, _) => {
+                let previous = Schema(&*self.0).fetch(pub_key);
+                events::wallet_stored(pub_key, &previous);
+                supply::Schema(&mut *self.0).update_wallets(previous.balance(), wallet.balance());
+                history::Schema(&mut *self.0).record(pub_key, previous, wallet.clone());
+                self.index_mut().put(pub_key, wallet)
+            }
         };
     }
 
-    /// Remove wallet state from the database.
+    /// Remove wallet state from the database, recording it in the wallet
+    /// history and the coin supply.
     pub fn remove(&mut self, pub_key: &PublicKey) {
-        self.index_mut().remove(pub_key);
+        if let Some(previous) = Schema(&*self.0).index().get(pub_key) {
+            events::wallet_stored(pub_key, &previous);
+            supply::Schema(&mut *self.0).update_wallets(previous.balance(), 0);
+            history::Schema(&mut *self.0).record(pub_key, previous, Wallet::new_empty());
+            self.index_mut().remove(pub_key);
+        }
     }
 }
@@ -21,12 +21,14 @@ extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
 extern crate chrono;
+extern crate env_logger;
 extern crate unicase;
 extern crate uuid;
 #[macro_use]
 extern crate prometheus;
 #[macro_use]
 extern crate lazy_static;
+extern crate num_cpus;
 extern crate extprim;
 extern crate percent_encoding;
 extern crate rand;
@@ -34,3 +36,4 @@ extern crate rand;
 pub mod config;
 pub mod currency;
 pub mod decimal;
+pub mod logging;
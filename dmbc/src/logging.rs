@@ -0,0 +1,110 @@
+//! Node logging.
+//!
+//! Records are written to stderr either in the usual text format or, with
+//! `log.format = "json"`, as one JSON object per line. Besides the level,
+//! module and message, JSON records carry the fields of the current
+//! `Context`, such as the hash and type of the transaction being executed,
+//! so that they can be indexed without parsing messages.
+//!
+//! Levels come from the `log` section of the configuration, and `RUST_LOG`
+//! still takes precedence when set.
+
+use std::cell::RefCell;
+use std::env;
+use std::io::{self, Write};
+
+use chrono::Utc;
+use env_logger::{Builder, Formatter};
+use exonum::crypto::Hash;
+use exonum::helpers;
+use log::{Record, SetLoggerError};
+use serde_json;
+
+use config;
+use currency::events::TxType;
+
+/// Fields attached to the records logged while they are entered.
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Context {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<Hash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_type: Option<TxType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<&'static str>,
+}
+
+thread_local! {
+    static CONTEXT: RefCell<Context> = RefCell::new(Context::default());
+}
+
+/// Restores the previous context when dropped.
+pub struct Entered {
+    previous: Context,
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        let previous = ::std::mem::replace(&mut self.previous, Context::default());
+        CONTEXT.with(|context| *context.borrow_mut() = previous);
+    }
+}
+
+/// Add the fields set in `fields` to the context of the current thread
+/// until the returned guard is dropped.
+pub fn enter(fields: Context) -> Entered {
+    CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        let previous = context.clone();
+        context.height = fields.height.or(context.height);
+        context.tx_hash = fields.tx_hash.or(context.tx_hash);
+        context.tx_type = fields.tx_type.or(context.tx_type);
+        context.result = fields.result.or(context.result);
+        Entered { previous }
+    })
+}
+
+/// Get the context of the current thread.
+pub fn context() -> Context {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+/// Render a record as a single-line JSON object.
+pub fn json_record(record: &Record, context: &Context) -> String {
+    let mut object = match serde_json::to_value(context) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    object.insert("ts".to_string(), json!(Utc::now().to_rfc3339()));
+    object.insert("level".to_string(), json!(record.level().to_string()));
+    object.insert("module".to_string(), json!(record.module_path()));
+    object.insert("msg".to_string(), json!(record.args().to_string()));
+    serde_json::Value::Object(object).to_string()
+}
+
+fn format_json(buf: &mut Formatter, record: &Record) -> io::Result<()> {
+    writeln!(buf, "{}", json_record(record, &context()))
+}
+
+/// Initialize the logger from the `log` section of the configuration.
+pub fn init(log: config::Log) -> Result<(), SetLoggerError> {
+    let mut builder = if log.clone().is_json() {
+        let mut builder = Builder::new();
+        builder.format(format_json);
+        builder
+    } else {
+        helpers::logger_builder()
+    };
+
+    builder.parse(&log.clone().level());
+    for directive in log.modules() {
+        builder.parse(&directive);
+    }
+    if let Ok(filters) = env::var("RUST_LOG") {
+        builder.parse(&filters);
+    }
+
+    builder.try_init()
+}
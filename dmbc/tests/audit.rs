@@ -0,0 +1,128 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit};
+use exonum::crypto;
+
+use dmbc::currency::assets::{AssetBundle, AssetInfo};
+use dmbc::currency::audit::{self, AssetMismatch};
+use dmbc::currency::wallet::{self, Wallet};
+
+#[test]
+fn audit_clean() {
+    let (creator_key, _) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_asset_to_wallet(&creator_key, (asset, info))
+        .create();
+
+    let fork = testkit.blockchain_mut().fork();
+    let report = audit::audit(&fork);
+
+    assert!(report.is_clean());
+    assert_eq!(report.assets, 1);
+}
+
+#[test]
+fn audit_amount_mismatch() {
+    let (creator_key, _) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_asset_to_wallet(&creator_key, (asset.clone(), info.clone()))
+        .create();
+
+    let drifted = AssetInfo::new(
+        info.creator(),
+        info.origin(),
+        15,
+        info.fees(),
+        info.data(),
+    );
+    testkit.store_asset_info(&asset.id(), drifted);
+
+    let fork = testkit.blockchain_mut().fork();
+    let report = audit::audit(&fork);
+
+    assert_eq!(
+        report.mismatched,
+        vec![AssetMismatch {
+            id: asset.id(),
+            info_amount: 15,
+            wallets_amount: 10,
+            escrowed_amount: 0,
+        }]
+    );
+    assert!(report.assets_without_info.is_empty());
+}
+
+#[test]
+fn audit_asset_without_info() {
+    let (creator_key, _) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        10,
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_asset_to_wallet(&creator_key, (asset.clone(), info))
+        .create();
+
+    let fork = {
+        let mut fork = testkit.blockchain_mut().fork();
+        dmbc::currency::assets::Schema(&mut fork).remove(&asset.id());
+        fork
+    };
+    let report = audit::audit(&fork);
+
+    assert_eq!(report.assets_without_info, vec![asset.id()]);
+}
+
+#[test]
+fn audit_holdings_overflow() {
+    let (creator_key, _) = crypto::gen_keypair();
+    let (holder_key, _) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        u64::max_value(),
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_asset_to_wallet(&creator_key, (asset.clone(), info))
+        .create();
+
+    let fork = {
+        let mut fork = testkit.blockchain_mut().fork();
+        let duplicate = AssetBundle::new(asset.id(), 1);
+        wallet::Schema(&mut fork).store(&holder_key, Wallet::new(0, vec![duplicate]));
+        fork
+    };
+    let report = audit::audit(&fork);
+
+    assert_eq!(report.overflowed, vec![asset.id()]);
+    assert!(report.mismatched.is_empty());
+    assert!(!report.is_clean());
+}
@@ -0,0 +1,137 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use std::env;
+use std::fs;
+
+use dmbc_testkit::{chain, served, DmbcTestApiBuilder};
+use hyper::header::{Authorization, Bearer, Headers};
+use hyper::status::StatusCode;
+use iron_test::{request, response};
+
+use dmbc::config;
+use dmbc::currency::api::auth::{ApiKeyAuth, API_KEY_HEADER};
+use dmbc::currency::api::error::ApiError;
+
+const URL: &str = "http://localhost:3000/api/services/cryptocurrency/v1/wallets";
+
+fn bearer(token: &str) -> Headers {
+    let mut headers = Headers::new();
+    headers.set(Authorization(Bearer {
+        token: token.to_string(),
+    }));
+    headers
+}
+
+fn api_key(key: &str) -> Headers {
+    let mut headers = Headers::new();
+    headers.set_raw(API_KEY_HEADER, vec![key.as_bytes().to_vec()]);
+    headers
+}
+
+#[test]
+fn auth_rejects_missing_or_unknown_key() {
+    let handler = chain(ApiKeyAuth::new(vec!["secret".to_string()]));
+
+    for headers in vec![Headers::new(), bearer("guess"), api_key("guess")] {
+        let response = served(request::get(URL, headers, &handler));
+        assert_eq!(response.status, Some(StatusCode::Unauthorized));
+        assert!(response.headers.get_raw("WWW-Authenticate").is_some());
+
+        let body = response::extract_body_to_string(response);
+        let result: Result<(), ApiError> = serde_json::from_str(&body).unwrap();
+        assert_eq!(result, Err(ApiError::Unauthorized));
+    }
+}
+
+#[test]
+fn auth_accepts_bearer_token_and_api_key() {
+    let handler = chain(ApiKeyAuth::new(vec![
+        "secret".to_string(),
+        "other".to_string(),
+    ]));
+
+    let response = served(request::get(URL, bearer("secret"), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let response = served(request::post(URL, api_key("other"), "{}", &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let response = served(request::options(URL, Headers::new(), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+}
+
+#[test]
+fn auth_groups_by_method() {
+    let handler = chain(ApiKeyAuth::by_method(vec![], vec!["submit".to_string()]));
+
+    let response = served(request::get(URL, Headers::new(), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let response = served(request::post(URL, Headers::new(), "{}", &handler));
+    assert_eq!(response.status, Some(StatusCode::Unauthorized));
+
+    let response = served(request::post(URL, bearer("submit"), "{}", &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+}
+
+#[test]
+fn auth_on_service_api() {
+    let path = env::temp_dir().join(format!("dmbc-auth-{}.toml", std::process::id()));
+    fs::write(
+        &path,
+        r#"
+        [auth.public]
+        keys = ["read"]
+
+        [auth.transactions]
+        keys = ["submit"]
+
+        [auth.private]
+        keys = ["ops"]
+        "#,
+    ).unwrap();
+    config::init(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+    let url = |endpoint: &str| {
+        format!(
+            "http://localhost:3000/api/services/cryptocurrency/{}",
+            endpoint
+        )
+    };
+
+    let public = api.public_handler();
+    let health = url("v1/health");
+    let response = served(request::get(&health, Headers::new(), public));
+    assert_eq!(response.status, Some(StatusCode::Unauthorized));
+    let response = served(request::get(&health, bearer("submit"), public));
+    assert_eq!(response.status, Some(StatusCode::Unauthorized));
+    let response = served(request::get(&health, bearer("read"), public));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let transactions = url("v1/transactions");
+    let response = served(request::post(&transactions, bearer("read"), "{}", public));
+    assert_eq!(response.status, Some(StatusCode::Unauthorized));
+    let response = served(request::post(&transactions, bearer("submit"), "{}", public));
+    assert_ne!(response.status, Some(StatusCode::Unauthorized));
+
+    let private = api.private_handler();
+    let metrics = url("metrics");
+    let response = served(request::get(&metrics, Headers::new(), private));
+    assert_eq!(response.status, Some(StatusCode::Unauthorized));
+    let response = served(request::get(&metrics, bearer("read"), private));
+    assert_eq!(response.status, Some(StatusCode::Unauthorized));
+    let response = served(request::get(&metrics, api_key("ops"), private));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+}
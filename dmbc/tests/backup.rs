@@ -0,0 +1,155 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate futures;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate router;
+#[macro_use]
+extern crate serde_json;
+
+use std::env;
+use std::fs;
+
+use exonum::blockchain::{self, Blockchain, GenesisConfig, ValidatorKeys};
+use exonum::crypto;
+use exonum::node::ApiSender;
+use exonum::storage::{RocksDB, RocksDBOptions};
+use exonum::api::Api;
+use futures::sync::mpsc;
+use hyper::header::{ContentType, Headers};
+use hyper::status::StatusCode;
+use iron_test::{request, response};
+use router::Router;
+
+use dmbc::currency::api::backup::{is_checkpoint_name, BackupApi, BackupResponse};
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::backup;
+use dmbc::currency::Service;
+
+#[test]
+fn backup_checkpoint_and_verify() {
+    let dir = env::temp_dir().join(format!("dmbc-backup-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut options = RocksDBOptions::default();
+    options.create_if_missing(true);
+    let db = RocksDB::open(dir.join("db"), &options).unwrap();
+
+    let (consensus_key, _) = crypto::gen_keypair();
+    let (service_key, service_secret_key) = crypto::gen_keypair();
+    let services: Vec<Box<blockchain::Service>> = vec![Box::new(Service::new())];
+    let mut blockchain = Blockchain::new(
+        Box::new(db.clone()),
+        services,
+        service_key,
+        service_secret_key,
+        ApiSender::new(mpsc::channel(1).0),
+    );
+    let validators = vec![ValidatorKeys {
+        consensus_key,
+        service_key,
+    }];
+    blockchain
+        .create_genesis_block(GenesisConfig::new(validators.into_iter()))
+        .unwrap();
+
+    let checkpoint = backup::create(&db, dir.join("checkpoint")).unwrap();
+    assert_eq!(checkpoint.height, 0);
+    assert_eq!(checkpoint.block_hash, blockchain.last_hash());
+    assert_eq!(backup::verify(dir.join("checkpoint")).unwrap(), checkpoint);
+
+    assert!(backup::create(&db, dir.join("checkpoint")).is_err());
+    assert!(backup::verify(dir.join("missing")).is_err());
+
+    let mut router = Router::new();
+    let api = BackupApi {
+        database: db.clone(),
+        backup_dir: dir.join("backups"),
+    };
+    api.wire(&mut router);
+    let post = |name: &str| -> (StatusCode, BackupResponse) {
+        let mut headers = Headers::new();
+        headers.set(ContentType::json());
+        let body = json!({ "name": name }).to_string();
+        let response =
+            request::post("http://localhost:3000/v1/backup", headers, &body, &router).unwrap();
+        let status = response.status.unwrap();
+        let body = response::extract_body_to_string(response);
+        (status, serde_json::from_str(&body).unwrap())
+    };
+
+    for name in &["../escape", "/tmp/escape", "a/b", "..", ""] {
+        assert_eq!(
+            post(name),
+            (StatusCode::BadRequest, Err(ApiError::BackupNameInvalid)),
+            "{}",
+            name
+        );
+    }
+    assert!(!dir.join("escape").exists());
+
+    let (status, response) = post("daily");
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().block_hash, blockchain.last_hash());
+    assert!(dir.join("backups").join("daily").exists());
+    assert_eq!(
+        post("daily"),
+        (StatusCode::Conflict, Err(ApiError::BackupExists))
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn backup_checkpoint_reopened() {
+    let dir = env::temp_dir().join(format!("dmbc-backup-reopened-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut options = RocksDBOptions::default();
+    options.create_if_missing(true);
+    let last_hash = {
+        let db = RocksDB::open(dir.join("db"), &options).unwrap();
+        let (consensus_key, _) = crypto::gen_keypair();
+        let (service_key, service_secret_key) = crypto::gen_keypair();
+        let services: Vec<Box<blockchain::Service>> = vec![Box::new(Service::new())];
+        let mut blockchain = Blockchain::new(
+            Box::new(db),
+            services,
+            service_key,
+            service_secret_key,
+            ApiSender::new(mpsc::channel(1).0),
+        );
+        let validators = vec![ValidatorKeys {
+            consensus_key,
+            service_key,
+        }];
+        blockchain
+            .create_genesis_block(GenesisConfig::new(validators.into_iter()))
+            .unwrap();
+        blockchain.last_hash()
+    };
+
+    // A reopened database has a handle for every column family, the default
+    // one included.
+    let db = RocksDB::open(dir.join("db"), &options).unwrap();
+    let checkpoint = backup::create(&db, dir.join("checkpoint")).unwrap();
+    assert_eq!(checkpoint.height, 0);
+    assert_eq!(checkpoint.block_hash, last_hash);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn backup_checkpoint_names() {
+    assert!(is_checkpoint_name("daily"));
+    assert!(is_checkpoint_name("2018-07-01.1"));
+    assert!(!is_checkpoint_name(""));
+    assert!(!is_checkpoint_name("."));
+    assert!(!is_checkpoint_name(".."));
+    assert!(!is_checkpoint_name("../db"));
+    assert!(!is_checkpoint_name("a..b"));
+    assert!(!is_checkpoint_name("/var/db"));
+    assert!(!is_checkpoint_name("a\\b"));
+    assert!(!is_checkpoint_name("a\nb"));
+}
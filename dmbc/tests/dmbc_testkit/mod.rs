@@ -5,6 +5,6 @@ extern crate serde_json;
 pub mod testkit;
 
 pub use self::testkit::{
-    asset_fees, create_asset, create_asset2, default_genesis_key, DmbcTestApiBuilder, DmbcTestKit,
-    DmbcTestKitApi,
+    asset_fees, chain, create_asset, create_asset2, default_genesis_key, served,
+    DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi,
 };
@@ -2,7 +2,8 @@ extern crate serde_json;
 
 use hyper::status::StatusCode;
 use iron::headers::{ContentType, Headers};
-use iron::Handler;
+use iron::prelude::*;
+use iron::{status, BeforeMiddleware, Handler};
 use iron_test::{request, response};
 
 use exonum::crypto::{self, PublicKey};
@@ -12,7 +13,10 @@ use exonum::messages::Message;
 use exonum_testkit::{TestKit as ExonumTestKit, TestKitApi as ExonumTestKitApi, TestKitBuilder};
 
 use dmbc::currency::api::fees::FeesResponse;
-use dmbc::currency::api::transaction::{StatusResponse, TxPostResponse};
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::{
+    SimulateResponse, StatusResponse, TransactionInfoResponse, TxPostResponse,
+};
 use dmbc::currency::api::offers::OpenOffersResult;
 use dmbc::currency::api::wallet as wallet_api;
 use dmbc::currency::api::wallet::{
@@ -23,6 +27,7 @@ use dmbc::currency::configuration::Configuration;
 use dmbc::currency::configuration::GENESIS_WALLET_PUB_KEY;
 use dmbc::currency::transactions::builders::fee;
 use dmbc::currency::offers::OpenOffers;
+use dmbc::currency::supply;
 use dmbc::currency::wallet::{self, Wallet};
 use dmbc::currency::{Service, SERVICE_NAME};
 use dmbc::decimal::UFract64;
@@ -41,6 +46,8 @@ pub trait DmbcTestKit {
     fn fetch_asset_info(&mut self, id: &AssetId) -> Option<AssetInfo>;
 
     fn store_asset_info(&mut self, id: &AssetId, info: AssetInfo);
+
+    fn assert_supply_conserved(&mut self);
 }
 
 impl DmbcTestKit for ExonumTestKit {
@@ -94,6 +101,7 @@ impl DmbcTestKit for ExonumTestKit {
             &pub_key,
             Wallet::new(updated_balance, existing_wallet.assets()),
         );
+        supply::Schema(&mut fork).mint(wallet.balance()).unwrap();
 
         assert!(blockchain.merge(fork.into_patch()).is_ok());
     }
@@ -112,6 +120,14 @@ impl DmbcTestKit for ExonumTestKit {
 
         assert!(blockchain.merge(fork.into_patch()).is_ok());
     }
+
+    fn assert_supply_conserved(&mut self) {
+        let blockchain = self.blockchain_mut();
+        let fork = blockchain.fork();
+        let supply = supply::Schema(&fork).audit();
+        assert!(supply.is_conserved(), "coin supply is not conserved: {:?}", supply);
+        assert_eq!(supply::Schema(&fork).totals(), supply);
+    }
 }
 
 pub trait DmbcTestKitApi {
@@ -133,6 +149,15 @@ pub trait DmbcTestKitApi {
     where
         for<'de> D: Deserialize<'de>;
 
+    fn get_private_with_status<D>(&self, endpoint: &str) -> (StatusCode, D)
+    where
+        for<'de> D: Deserialize<'de>;
+
+    fn post_private_with_status<T, D>(&self, endpoint: &str, body: &T) -> (StatusCode, D)
+    where
+        T: Serialize,
+        for<'de> D: Deserialize<'de>;
+
     fn post_raw_with_status2<D>(
         &self,
         endpoint: &str,
@@ -150,10 +175,18 @@ pub trait DmbcTestKitApi {
     where
         T: Message + Serialize;
 
+    fn get_tx_info<T>(&self, transaction: &T) -> (StatusCode, TransactionInfoResponse)
+    where
+        T: Message + Serialize;
+
     fn post_fee<T>(&self, tx: &T) -> (StatusCode, FeesResponse)
     where
         T: Message + Serialize;
 
+    fn simulate_tx<T>(&self, tx: &T) -> (StatusCode, SimulateResponse)
+    where
+        T: Message + Serialize;
+
     fn get_wallet(&self, public_key: &PublicKey) -> WalletInfo;
 
     fn get_wallet_assets(&self, public_key: &PublicKey) -> Vec<ExtendedAsset>;
@@ -207,6 +240,37 @@ impl DmbcTestKitApi for ExonumTestKitApi {
         (status, serde_json::from_str(&body).unwrap())
     }
 
+    fn get_private_with_status<D>(&self, endpoint: &str) -> (StatusCode, D)
+    where
+        for<'de> D: Deserialize<'de>,
+    {
+        ExonumTestKitApi::get_internal_with_status(self.private_handler(), endpoint)
+    }
+
+    fn post_private_with_status<T, D>(&self, endpoint: &str, body: &T) -> (StatusCode, D)
+    where
+        T: Serialize,
+        for<'de> D: Deserialize<'de>,
+    {
+        let url = format!(
+            "http://localhost:3000/api/services/{}/{}",
+            SERVICE_NAME, endpoint
+        );
+        let response = request::post(
+            &url,
+            {
+                let mut headers = Headers::new();
+                headers.set(ContentType::json());
+                headers
+            },
+            &serde_json::to_string(body).expect("Cannot serialize data to JSON"),
+            self.private_handler(),
+        ).expect("Cannot send data");
+        let status = response.status.unwrap();
+        let body = response::extract_body_to_string(response);
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
     fn post_raw_with_status2<D>(
         &self,
         endpoint: &str,
@@ -246,6 +310,16 @@ impl DmbcTestKitApi for ExonumTestKitApi {
     }
 
     fn get_tx_status<T>(&self, transaction: &T) -> (StatusCode, StatusResponse)
+    where
+        T: Message + Serialize,
+    {
+        let (status, response) = self.get_tx_info(transaction);
+        let tx_status =
+            response.and_then(|info| info.status.ok_or(ApiError::TransactionNotFound));
+        (status, tx_status)
+    }
+
+    fn get_tx_info<T>(&self, transaction: &T) -> (StatusCode, TransactionInfoResponse)
     where
         T: Message + Serialize,
     {
@@ -260,6 +334,13 @@ impl DmbcTestKitApi for ExonumTestKitApi {
         self.post_with_status("/v1/fees/transactions", &tx)
     }
 
+    fn simulate_tx<T>(&self, tx: &T) -> (StatusCode, SimulateResponse)
+    where
+        T: Message + Serialize,
+    {
+        self.post_with_status("/v1/transactions/simulate", &tx)
+    }
+
     fn get_wallet(&self, public_key: &PublicKey) -> WalletInfo {
         let (status, response): (StatusCode, WalletResponse) =
             self.get_with_status(&format!("/v1/wallets/{}", public_key.to_string()));
@@ -335,6 +416,19 @@ pub fn default_genesis_key() -> PublicKey {
     PublicKey::from_hex(GENESIS_WALLET_PUB_KEY).unwrap()
 }
 
+/// Handler answering `{}` to every request that `middleware` lets through.
+pub fn chain<M: BeforeMiddleware>(middleware: M) -> Chain {
+    let mut chain = Chain::new(|_: &mut Request| Ok(Response::with((status::Ok, "{}"))));
+    chain.link_before(middleware);
+    chain
+}
+
+/// Response of a request, including the ones rejected by middleware, which
+/// the server turns into responses as well.
+pub fn served(result: IronResult<Response>) -> Response {
+    result.unwrap_or_else(|e| e.response)
+}
+
 pub struct DmbcTestApiBuilder {
     configuration: Option<Configuration>,
     wallets: Vec<(PublicKey, Wallet)>,
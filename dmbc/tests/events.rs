@@ -0,0 +1,350 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::crypto;
+use exonum::messages::Message;
+use hyper::status::StatusCode;
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::api::events::SubscribeResponse;
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
+use dmbc::currency::error::Error;
+use dmbc::currency::events::{self, v1, Event, Filter};
+use dmbc::currency::events::v1::{BlockCommitted, TransactionCommitted, TxResult, TxType, WalletChange};
+use dmbc::currency::transactions::components::{FeeItem, FeeReason};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn events_transfer() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, TransactionPermissions::default()))
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let receiver = events::subscribe(Filter {
+        wallet: Some(recipient_key),
+        ..Filter::default()
+    }).unwrap();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let timeout = Duration::from_secs(5);
+    let mut received = None;
+    while let Ok(event) = receiver.recv_timeout(timeout) {
+        if let Event::Transaction(committed) = event {
+            received = Some(committed);
+            break;
+        }
+    }
+
+    let committed = received.expect("no transaction event");
+    let genesis_key = dmbc_testkit::default_genesis_key();
+
+    assert_eq!(committed.version, v1::VERSION);
+    assert_eq!(committed.tx_hash, tx_transfer.hash());
+    assert_eq!(committed.height, 1);
+    assert_eq!(committed.index, 0);
+    assert_eq!(committed.tx_type, TxType::Transfer);
+    assert!(committed.result.is_success());
+    assert_eq!(committed.wallets, vec![public_key, recipient_key]);
+    assert_eq!(
+        committed.changes,
+        vec![
+            WalletChange {
+                wallet: public_key,
+                coins: -1010,
+                assets: vec![],
+            },
+            WalletChange {
+                wallet: recipient_key,
+                coins: 10,
+                assets: vec![],
+            },
+            WalletChange {
+                wallet: genesis_key,
+                coins: 1000,
+                assets: vec![],
+            },
+        ]
+    );
+    assert_eq!(
+        committed.fees,
+        vec![FeeItem::new(&public_key, &genesis_key, FeeReason::Blockchain, transaction_fee)]
+    );
+}
+
+#[test]
+fn events_failed_transfer() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(5, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let receiver = events::subscribe(Filter {
+        wallet: Some(recipient_key),
+        ..Filter::default()
+    }).unwrap();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let timeout = Duration::from_secs(5);
+    let mut received = None;
+    while let Ok(event) = receiver.recv_timeout(timeout) {
+        if let Event::Transaction(committed) = event {
+            received = Some(committed);
+            break;
+        }
+    }
+
+    let committed = received.expect("no transaction event");
+    assert_eq!(committed.result, TxResult::from(Err(Error::InsufficientFunds)));
+    assert_eq!(committed.result.code, Error::InsufficientFunds as u8);
+    assert!(committed.fees.is_empty());
+}
+
+fn next_committed(receiver: &Receiver<Event>) -> TransactionCommitted {
+    let timeout = Duration::from_secs(5);
+    while let Ok(event) = receiver.recv_timeout(timeout) {
+        if let Event::Transaction(committed) = event {
+            return committed;
+        }
+    }
+    panic!("no transaction event");
+}
+
+#[test]
+fn events_failed_transfer_pays_blockchain_fee() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, TransactionPermissions::default()))
+        .add_wallet_value(&public_key, Wallet::new(transaction_fee, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let receiver = events::subscribe(Filter {
+        wallet: Some(recipient_key),
+        ..Filter::default()
+    }).unwrap();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let committed = next_committed(&receiver);
+    let genesis_key = dmbc_testkit::default_genesis_key();
+
+    assert_eq!(committed.result, TxResult::from(Err(Error::InsufficientFunds)));
+    assert_eq!(
+        committed.changes,
+        vec![
+            WalletChange {
+                wallet: public_key,
+                coins: -(transaction_fee as i64),
+                assets: vec![],
+            },
+            WalletChange {
+                wallet: genesis_key,
+                coins: transaction_fee as i64,
+                assets: vec![],
+            },
+        ]
+    );
+    assert_eq!(
+        committed.fees,
+        vec![FeeItem::new(&public_key, &genesis_key, FeeReason::Blockchain, transaction_fee)]
+    );
+}
+
+#[test]
+fn events_unmatched_bid_offer_pays_no_royalty() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let (creator_key, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        2,
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, TransactionPermissions::default()))
+        .add_asset_info(&asset.id(), info)
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![asset.clone()]))
+        .create();
+    let api = testkit.api();
+
+    let receiver = events::subscribe(Filter {
+        wallet: Some(public_key),
+        ..Filter::default()
+    }).unwrap();
+
+    let tx_bid = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_offer()
+        .asset(TradeAsset::from_bundle(asset, 1000))
+        .data_info("bid")
+        .bid_build();
+
+    api.post_tx(&tx_bid);
+    testkit.create_block();
+
+    let committed = next_committed(&receiver);
+    let genesis_key = dmbc_testkit::default_genesis_key();
+
+    assert!(committed.result.is_success());
+    assert!(committed.changes.iter().all(|change| change.wallet != creator_key));
+    assert_eq!(
+        committed.fees,
+        vec![FeeItem::new(&public_key, &genesis_key, FeeReason::Blockchain, transaction_fee)]
+    );
+}
+
+#[test]
+fn events_filter() {
+    let (sender, _) = crypto::gen_keypair();
+    let (recipient, _) = crypto::gen_keypair();
+    let (stranger, _) = crypto::gen_keypair();
+
+    let event = Event::Transaction(TransactionCommitted {
+        version: v1::VERSION,
+        tx_hash: crypto::hash(&[1, 2, 3]),
+        height: 1,
+        index: 0,
+        tx_type: TxType::Transfer,
+        wallets: vec![sender, recipient],
+        assets: vec![],
+        changes: vec![],
+        fees: vec![],
+        result: TxResult::from(Ok(())),
+    });
+    let block = Event::Block(BlockCommitted {
+        version: v1::VERSION,
+        height: 1,
+        hash: crypto::hash(&[4, 5, 6]),
+        tx_count: 1,
+    });
+
+    let by_wallet = Filter {
+        wallet: Some(recipient),
+        ..Filter::default()
+    };
+    let by_stranger = Filter {
+        wallet: Some(stranger),
+        ..Filter::default()
+    };
+    let by_type = Filter {
+        tx_type: Some(TxType::Trade),
+        ..Filter::default()
+    };
+
+    assert!(Filter::default().matches(&event));
+    assert!(by_wallet.matches(&event));
+    assert!(!by_stranger.matches(&event));
+    assert!(!by_type.matches(&event));
+    assert!(by_stranger.matches(&block));
+}
+
+#[test]
+fn events_invalid_filter() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, SubscribeResponse) =
+        api.get_with_status("/v1/events?wallet=invalid");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::WalletHexInvalid));
+
+    let (status, response): (StatusCode, SubscribeResponse) =
+        api.get_with_status("/v1/events?tx_type=mint");
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::TxTypeInvalid));
+}
+
+#[test]
+fn events_effects_pruned() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let transfer = |seed| {
+        transaction::Builder::new()
+            .keypair(public_key, secret_key.clone())
+            .tx_transfer()
+            .amount(10)
+            .recipient(recipient_key)
+            .seed(seed)
+            .build()
+    };
+    let first = transfer(1);
+    let second = transfer(2);
+
+    api.post_tx(&first);
+    testkit.create_block();
+    let snapshot = testkit.blockchain_mut().snapshot();
+    assert!(events::Schema(snapshot.as_ref()).fetch(&first.hash()).is_some());
+
+    api.post_tx(&second);
+    testkit.create_block();
+    let snapshot = testkit.blockchain_mut().snapshot();
+    assert_eq!(events::Schema(snapshot.as_ref()).fetch(&first.hash()), None);
+    assert!(events::Schema(snapshot.as_ref()).fetch(&second.hash()).is_some());
+}
@@ -15,7 +15,6 @@ use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 use exonum::crypto;
 use hyper::status::StatusCode;
 
-use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::assets::MetaAsset;
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::transactions::builders::transaction;
@@ -54,5 +53,5 @@ fn fees_for_add_assets() {
     expected.insert(public_key, transaction_fee + amount * per_asset_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
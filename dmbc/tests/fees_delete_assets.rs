@@ -15,7 +15,6 @@ use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 use exonum::crypto;
 use hyper::status::StatusCode;
 
-use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::transactions::builders::transaction;
 
@@ -46,5 +45,5 @@ fn fees_for_delete_assets() {
     expected.insert(public_key, transaction_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
@@ -15,7 +15,6 @@ use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 use exonum::crypto;
 use hyper::status::StatusCode;
 
-use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::error::Error;
 use dmbc::currency::transactions::builders::transaction;
@@ -90,7 +89,7 @@ fn fees_for_exchange_recipient() {
     expected.insert(recipient_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -162,7 +161,7 @@ fn fees_for_exchange_sender() {
     expected.insert(sender_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -235,7 +234,7 @@ fn fees_for_exchange_recipient_and_sender() {
     expected.insert(recipient_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -308,7 +307,7 @@ fn fees_for_exchange_recipient_and_sender_creator() {
     expected.insert(recipient_public_key, expected_recipient_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -0,0 +1,122 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::crypto;
+use hyper::status::StatusCode;
+
+use dmbc::currency::assets::TradeAsset;
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::{FeeItem, FeeReason};
+
+#[test]
+fn fees_for_bid_offer() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let price_per_unit = 1000;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let permissions = TransactionPermissions::default();
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        meta_data,
+        units,
+        dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()),
+        &creator_pub_key,
+    );
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_asset_to_wallet(&public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_bid = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_offer()
+        .asset(TradeAsset::from_bundle(asset, price_per_unit))
+        .data_info("bid")
+        .bid_build();
+
+    let (status, response) = api.post_fee(&tx_bid);
+
+    let mut expected = HashMap::new();
+    expected.insert(public_key, transaction_fee + fixed * units);
+
+    let expected_items = vec![
+        FeeItem::new(
+            &public_key,
+            &dmbc_testkit::default_genesis_key(),
+            FeeReason::Blockchain,
+            transaction_fee,
+        ),
+        FeeItem::new(
+            &public_key,
+            &creator_pub_key,
+            FeeReason::CreatorRoyalty,
+            fixed * units,
+        ),
+    ];
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap().unwrap();
+    assert_eq!(body.fees, expected);
+    assert_eq!(body.items, expected_items);
+}
+
+#[test]
+fn fees_for_ask_offer() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let price_per_unit = 1000;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let permissions = TransactionPermissions::default();
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        meta_data,
+        units,
+        dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()),
+        &creator_pub_key,
+    );
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_asset_info(&asset.id(), info)
+        .create();
+    let api = testkit.api();
+
+    let tx_ask = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_offer()
+        .asset(TradeAsset::from_bundle(asset, price_per_unit))
+        .data_info("ask")
+        .ask_build();
+
+    let (status, response) = api.post_fee(&tx_ask);
+
+    let mut expected = HashMap::new();
+    expected.insert(public_key, transaction_fee + fixed * units);
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
+}
@@ -15,7 +15,6 @@ use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 use exonum::crypto;
 use hyper::status::StatusCode;
 
-use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::assets::TradeAsset;
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::error::Error;
@@ -64,7 +63,7 @@ fn fees_for_trade_recipient() {
     expected.insert(buyer_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -109,7 +108,7 @@ fn fees_for_trade_sender() {
     expected.insert(seller_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -155,7 +154,7 @@ fn fees_for_trade_recipient_and_sender() {
     expected.insert(seller_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -201,7 +200,7 @@ fn fees_for_trade_recipient_and_sender_creator() {
     expected.insert(buyer_public_key, expected_fee_buyer);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
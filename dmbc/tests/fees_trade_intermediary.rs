@@ -15,12 +15,11 @@ use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 use exonum::crypto;
 use hyper::status::StatusCode;
 
-use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::assets::TradeAsset;
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::error::Error;
 use dmbc::currency::transactions::builders::transaction;
-use dmbc::currency::transactions::components::FeeStrategy;
+use dmbc::currency::transactions::components::{FeeItem, FeeReason, FeeStrategy};
 
 #[test]
 fn fees_for_trade_intermediary_recipient() {
@@ -66,7 +65,7 @@ fn fees_for_trade_intermediary_recipient() {
     expected.insert(buyer_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -113,7 +112,7 @@ fn fees_for_trade_intermediary_sender() {
     expected.insert(seller_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -161,7 +160,7 @@ fn fees_for_trade_intermediary_recipient_and_sender() {
     expected.insert(seller_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -208,7 +207,7 @@ fn fees_for_trade_intermediary_intermedniary() {
     expected.insert(intermediary_public_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -256,7 +255,7 @@ fn fees_for_trade_intermediary_recipient_and_sender_creator() {
     expected.insert(buyer_public_key, expected_buyer_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
@@ -301,3 +300,70 @@ fn fees_for_trade_intermediary_asset_not_found() {
     assert_eq!(status, StatusCode::BadRequest);
     assert_eq!(response, Ok(Err(Error::AssetNotFound)));
 }
+
+#[test]
+fn fees_for_trade_intermediary_itemized() {
+    let transaction_fee = 1000;
+    let fixed = 10;
+    let units = 2;
+    let price_per_unit = 1000;
+    let commission = 50;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let permissions = TransactionPermissions::default();
+
+    let (creator_pub_key, _) = crypto::gen_keypair();
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+    let (intermediary_public_key, intermediary_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        meta_data,
+        units,
+        dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()),
+        &creator_pub_key,
+    );
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets_with_intermediary()
+        .add_asset_value(TradeAsset::from_bundle(asset, price_per_unit))
+        .seller(seller_public_key, seller_secret_key)
+        .intermediary_key_pair(intermediary_public_key, intermediary_secret_key)
+        .commission(commission)
+        .fee_strategy(FeeStrategy::Recipient)
+        .seed(12)
+        .build();
+
+    let (status, response) = api.post_fee(&tx_trade);
+
+    let expected_items = vec![
+        FeeItem::new(
+            &buyer_public_key,
+            &dmbc_testkit::default_genesis_key(),
+            FeeReason::Blockchain,
+            transaction_fee,
+        ),
+        FeeItem::new(
+            &buyer_public_key,
+            &creator_pub_key,
+            FeeReason::CreatorRoyalty,
+            fixed * units,
+        ),
+        FeeItem::new(
+            &buyer_public_key,
+            &intermediary_public_key,
+            FeeReason::IntermediaryCommission,
+            commission,
+        ),
+    ];
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.map(|r| r.map(|body| body.items)), Ok(Ok(expected_items)));
+}
@@ -15,10 +15,10 @@ use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
 use exonum::crypto;
 use hyper::status::StatusCode;
 
-use dmbc::currency::api::fees::FeesResponseBody;
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::error::Error;
 use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::{FeeItem, FeeReason};
 
 #[test]
 fn fees_for_transfer() {
@@ -61,7 +61,64 @@ fn fees_for_transfer() {
     expected.insert(sender_pub_key, expected_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
+}
+
+#[test]
+fn fees_for_transfer_items_sorted() {
+    let transaction_fee = 1000;
+    let amount = 2;
+    let fixed = 10;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let permissions = TransactionPermissions::default();
+
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (sender_pub_key, sender_sec_key) = crypto::gen_keypair();
+
+    let mut builder = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions));
+    let mut tx_builder = transaction::Builder::new()
+        .keypair(sender_pub_key, sender_sec_key)
+        .tx_transfer();
+    let mut creators = Vec::new();
+    for meta_data in &["asset_a", "asset_b", "asset_c", "asset_d"] {
+        let (creator_key, _) = crypto::gen_keypair();
+        let (asset, info) = dmbc_testkit::create_asset(
+            meta_data,
+            amount,
+            dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()),
+            &creator_key,
+        );
+        builder = builder.add_asset_to_wallet(&sender_pub_key, (asset.clone(), info));
+        tx_builder = tx_builder.add_asset_value(asset);
+        creators.push(creator_key);
+    }
+    let testkit = builder.create();
+    let api = testkit.api();
+
+    let tx_transfer = tx_builder.recipient(recipient_key).seed(42).build();
+
+    let (status, response) = api.post_fee(&tx_transfer);
+
+    // The blockchain fee comes first, then the royalties by recipient.
+    creators.sort();
+    let mut expected_items = vec![FeeItem::new(
+        &sender_pub_key,
+        &dmbc_testkit::default_genesis_key(),
+        FeeReason::Blockchain,
+        transaction_fee,
+    )];
+    for creator_key in &creators {
+        expected_items.push(FeeItem::new(
+            &sender_pub_key,
+            creator_key,
+            FeeReason::CreatorRoyalty,
+            amount * fixed,
+        ));
+    }
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.map(|r| r.map(|body| body.items)), Ok(Ok(expected_items)));
 }
 
 #[test]
@@ -103,7 +160,7 @@ fn fees_for_transfer_sender_is_creator() {
     expected.insert(sender_pub_key, transaction_fee);
 
     assert_eq!(status, StatusCode::Ok);
-    assert_eq!(response, Ok(Ok(FeesResponseBody { fees: expected })));
+    assert_eq!(response.map(|r| r.map(|body| body.fees)), Ok(Ok(expected)));
 }
 
 #[test]
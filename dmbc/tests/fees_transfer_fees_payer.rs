@@ -0,0 +1,82 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use std::collections::HashMap;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::crypto;
+use hyper::status::StatusCode;
+
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::transactions::components::{FeeItem, FeeReason};
+
+#[test]
+fn fees_for_transfer_with_fees_payer() {
+    let transaction_fee = 1000;
+    let amount = 2;
+    let fixed = 10;
+    let meta_data = "asset";
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let permissions = TransactionPermissions::default();
+
+    let (creator_key, _) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+    let (sender_pub_key, sender_sec_key) = crypto::gen_keypair();
+    let (fees_payer_pk, fees_payer_sk) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        meta_data,
+        amount,
+        dmbc_testkit::asset_fees(fixed, "0.0".parse().unwrap()),
+        &creator_key,
+    );
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_asset_to_wallet(&sender_pub_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(sender_pub_key, sender_sec_key)
+        .tx_transfer_with_fees_payer()
+        .fees_payer(fees_payer_pk, fees_payer_sk)
+        .add_asset_value(asset)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let (status, response) = api.post_fee(&tx_transfer);
+
+    let mut expected = HashMap::new();
+    expected.insert(fees_payer_pk, transaction_fee + amount * fixed);
+
+    let expected_items = vec![
+        FeeItem::new(
+            &fees_payer_pk,
+            &dmbc_testkit::default_genesis_key(),
+            FeeReason::Blockchain,
+            transaction_fee,
+        ),
+        FeeItem::new(
+            &fees_payer_pk,
+            &creator_key,
+            FeeReason::CreatorRoyalty,
+            amount * fixed,
+        ),
+    ];
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap().unwrap();
+    assert_eq!(body.fees, expected);
+    assert_eq!(body.items, expected_items);
+}
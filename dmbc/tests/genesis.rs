@@ -0,0 +1,144 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+
+use std::env;
+use std::fs;
+
+use exonum::blockchain::ValidatorKeys;
+use exonum::crypto;
+use exonum_testkit::TestKitBuilder;
+
+use dmbc::currency;
+use dmbc::currency::assets::{self, AssetId};
+use dmbc::currency::configuration::Configuration;
+use dmbc::currency::genesis::{Genesis, GenesisAsset, GenesisAssetFees, GenesisWallet};
+use dmbc::currency::{supply, wallet};
+
+fn write_temp(name: &str, content: &str) -> String {
+    let path = env::temp_dir().join(format!("dmbc-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn genesis_from_file() {
+    let (validator, _) = crypto::gen_keypair();
+    let (service, _) = crypto::gen_keypair();
+    let (recipient, _) = crypto::gen_keypair();
+    let (holder, _) = crypto::gen_keypair();
+
+    let content = format!(
+        r#"
+        [[validators]]
+        consensus_key = "{}"
+        service_key = "{}"
+
+        [consensus]
+        round_timeout = 3000
+        status_timeout = 5000
+        peers_timeout = 10000
+        txs_block_limit = 1000
+        max_message_len = 1048576
+        timeout_adjuster = {{ type = "Constant", timeout = 500 }}
+
+        [fees]
+        recipient = "{}"
+        transfer = 1000
+
+        [permissions]
+        global_permission_mask = "0x00000000000000ff"
+
+        [[wallets]]
+        pub_key = "{}"
+        balance = 500
+
+        [[wallets.assets]]
+        data = "gold"
+        amount = 10
+        fees = {{ transfer = {{ fixed = 10, fraction = "0.01" }} }}
+        "#,
+        validator.to_hex(),
+        service.to_hex(),
+        recipient.to_hex(),
+        holder.to_hex(),
+    );
+    let path = write_temp("genesis.toml", &content);
+    let genesis = Genesis::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(genesis.validate(), Ok(()));
+    assert_eq!(
+        genesis.validators,
+        vec![ValidatorKeys {
+            consensus_key: validator,
+            service_key: service,
+        }]
+    );
+
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(1)
+        .with_service(currency::Service::with_genesis(genesis.clone()))
+        .create();
+    let fork = testkit.blockchain_mut().fork();
+
+    let configuration = Configuration::extract(&fork);
+    assert_eq!(configuration, genesis.configuration());
+    assert_eq!(configuration.fees().recipient(), &recipient);
+    assert_eq!(configuration.fees().transfer(), 1000);
+    assert_eq!(configuration.permissions().global_permission_mask(), 0xff);
+
+    let id = AssetId::from_data("gold", &holder);
+    let wallet = wallet::Schema(&fork).fetch(&holder);
+    assert_eq!(wallet.balance(), 500);
+    assert_eq!(wallet.assets().len(), 1);
+    assert_eq!(wallet.assets()[0].id(), id);
+
+    let info = assets::Schema(&fork).fetch(&id).unwrap();
+    assert_eq!(info.amount(), 10);
+    assert_eq!(info.creator(), &holder);
+    assert_eq!(info.fees().transfer().fixed(), 10);
+
+    assert_eq!(supply::Schema(&fork).fetch(), 500);
+}
+
+#[test]
+fn genesis_default_roundtrip() {
+    let genesis = Genesis::default();
+    let path = write_temp("default-genesis.toml", &genesis.to_toml());
+    let loaded = Genesis::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, genesis);
+    assert_eq!(loaded.validate(), Ok(()));
+    assert_eq!(loaded.wallets[0].balance, supply::GENESIS_SUPPLY);
+}
+
+#[test]
+fn genesis_validation() {
+    let (holder, _) = crypto::gen_keypair();
+    let wallet = GenesisWallet {
+        pub_key: holder,
+        balance: u64::max_value(),
+        assets: vec![GenesisAsset {
+            data: "gold".to_string(),
+            amount: 0,
+            creator: None,
+            fees: GenesisAssetFees::default(),
+        }],
+    };
+
+    let mut genesis = Genesis::default();
+    genesis.validators.clear();
+    genesis.wallets = vec![wallet.clone(), wallet];
+    genesis.wallets[1].assets[0].fees.trade.fraction = "lots".to_string();
+    genesis.consensus.txs_block_limit = 0;
+
+    let errors = genesis.validate().unwrap_err();
+    assert!(errors.iter().any(|e| e.starts_with("validators:")));
+    assert!(errors.iter().any(|e| e.starts_with("consensus.txs_block_limit:")));
+    assert!(errors.iter().any(|e| e.starts_with("wallets[1].pub_key:")));
+    assert!(errors.iter().any(|e| e.starts_with("wallets[1].balance:")));
+    assert!(errors.iter().any(|e| e.starts_with("wallets[0].assets[0].amount:")));
+    assert!(errors.iter().any(|e| e.starts_with("wallets[1].assets[0].fees:")));
+}
@@ -0,0 +1,111 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate futures;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate router;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::api::Api;
+use exonum::blockchain::{self, Blockchain, GenesisConfig, SharedNodeState, ValidatorKeys};
+use exonum::crypto;
+use exonum::helpers::Height;
+use exonum::node::ApiSender;
+use exonum::storage::MemoryDB;
+use futures::sync::mpsc;
+use hyper::header::Headers;
+use hyper::status::StatusCode;
+use iron_test::{request, response};
+use router::Router;
+
+use dmbc::config;
+use dmbc::currency::api::health::{HealthApi, HealthResponse};
+use dmbc::currency::Service;
+
+#[test]
+fn health_report() {
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    testkit.create_block();
+
+    let (status, response): (StatusCode, HealthResponse) = api.get_with_status("/v1/health");
+
+    assert_eq!(status, StatusCode::Ok);
+    let report = response.unwrap();
+    assert_eq!(report.height, 1);
+    assert!(report.last_block_age_ms.is_some());
+    assert!(report.is_validator);
+    assert!(report.configuration_current);
+    assert_eq!(report.lag, 0);
+    assert!(report.ready);
+    assert!(!report.nats.enabled);
+}
+
+#[test]
+fn health_ready() {
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    testkit.create_block();
+
+    let (status, response): (StatusCode, HealthResponse) = api.get_with_status("/v1/ready");
+
+    assert_eq!(status, StatusCode::Ok);
+    assert!(response.unwrap().ready);
+}
+
+#[test]
+fn health_not_ready_when_lagging() {
+    let (consensus_key, _) = crypto::gen_keypair();
+    let (service_key, service_secret_key) = crypto::gen_keypair();
+    let services: Vec<Box<blockchain::Service>> = vec![Box::new(Service::new())];
+    let mut blockchain = Blockchain::new(
+        Box::new(MemoryDB::new()),
+        services,
+        service_key,
+        service_secret_key,
+        ApiSender::new(mpsc::channel(1).0),
+    );
+    let validators = vec![ValidatorKeys {
+        consensus_key,
+        service_key,
+    }];
+    blockchain
+        .create_genesis_block(GenesisConfig::new(validators.into_iter()))
+        .unwrap();
+
+    // Peers work on the height after their last block, which is `max_lag + 1`
+    // blocks ahead of the genesis block here.
+    let max_lag = config::config().health().max_lag();
+    let node_state = SharedNodeState::new(1000);
+    node_state.update_net_height(Height(max_lag + 2));
+
+    let api = HealthApi {
+        blockchain,
+        tx_pool: None,
+        node_state: Some(node_state),
+        service_key,
+    };
+    let mut router = Router::new();
+    api.wire(&mut router);
+
+    let url = "http://localhost:3000/v1/ready";
+    let response = request::get(url, Headers::new(), &router).unwrap();
+
+    assert_eq!(response.status, Some(StatusCode::ServiceUnavailable));
+    let body = response::extract_body_to_string(response);
+    let response: HealthResponse = serde_json::from_str(&body).unwrap();
+    let report = response.unwrap();
+    assert_eq!(report.height, 0);
+    assert_eq!(report.net_height, Some(max_lag + 1));
+    assert_eq!(report.lag, max_lag + 1);
+    assert_eq!(report.max_lag, max_lag);
+    assert!(!report.ready);
+}
@@ -14,7 +14,9 @@ use std::collections::HashMap;
 use dmbc_testkit::{DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum_testkit::TestKit;
+use hyper::header::Headers;
 use hyper::status::StatusCode;
+use iron_test::request;
 
 use dmbc::currency::api::assets_intern::{
     AssetIdBatchRequest, AssetIdBatchResponse, AssetIdBatchResponseBody, AssetIdRequest,
@@ -31,7 +33,7 @@ fn intern_assets_id_from_meta() {
 
     let (pub_key, _) = crypto::gen_keypair();
 
-    let (status, response): (StatusCode, AssetIdResponse) = api.get_with_status(&format!(
+    let (status, response): (StatusCode, AssetIdResponse) = api.get_private_with_status(&format!(
         "/v1/intern/assets/{}/{}",
         pub_key.to_string(),
         meta_data
@@ -51,7 +53,7 @@ fn intern_assets_id_from_meta_invalid_public_key() {
     let api = testkit.api();
 
     let (status, response): (StatusCode, AssetIdResponse) =
-        api.get_with_status("/v1/intern/assets/invalidpublickey/meta_dummy");
+        api.get_private_with_status("/v1/intern/assets/invalidpublickey/meta_dummy");
 
     assert_eq!(status, StatusCode::BadRequest);
     assert_eq!(response, Err(ApiError::WalletHexInvalid));
@@ -70,7 +72,7 @@ fn intern_assets_ids_from_meta() {
     let id1 = AssetId::from_data(meta_data1, &pub_key);
     let assets = vec![meta_data0.to_string(), meta_data1.to_string()];
 
-    let (status, response): (StatusCode, AssetIdResponse) = api.post_with_status(
+    let (status, response): (StatusCode, AssetIdResponse) = api.post_private_with_status(
         &format!("/v1/intern/assets/{}", pub_key.to_string()),
         &AssetIdRequest { assets },
     );
@@ -92,7 +94,7 @@ fn intern_assets_ids_from_meta_invalid_public_key() {
     let meta_data1 = "asset1";
     let assets = vec![meta_data0.to_string(), meta_data1.to_string()];
 
-    let (status, response): (StatusCode, AssetIdResponse) = api.post_with_status(
+    let (status, response): (StatusCode, AssetIdResponse) = api.post_private_with_status(
         "/v1/intern/assets/invalidpublickey",
         &AssetIdRequest { assets },
     );
@@ -116,7 +118,7 @@ fn intern_assets_batch_ids() {
     assets_map.insert(pub_key0.to_string(), assets.clone());
     assets_map.insert(pub_key1.to_string(), assets);
 
-    let (status, response): (StatusCode, AssetIdBatchResponse) = api.post_with_status(
+    let (status, response): (StatusCode, AssetIdBatchResponse) = api.post_private_with_status(
         "/v1/intern/assets",
         &AssetIdBatchRequest { assets: assets_map },
     );
@@ -158,7 +160,7 @@ fn intern_assets_batch_ids_invalid_public_key() {
     let mut assets_map = HashMap::new();
     assets_map.insert("InvalidPublicKey".to_string(), assets);
 
-    let (status, response): (StatusCode, AssetIdBatchResponse) = api.post_with_status(
+    let (status, response): (StatusCode, AssetIdBatchResponse) = api.post_private_with_status(
         "/v1/intern/assets",
         &AssetIdBatchRequest { assets: assets_map },
     );
@@ -176,7 +178,7 @@ fn intern_assets_endcoded_query() {
 
     let (pub_key, _) = crypto::gen_keypair();
 
-    let (status, response): (StatusCode, AssetIdResponse) = api.get_with_status(&format!(
+    let (status, response): (StatusCode, AssetIdResponse) = api.get_private_with_status(&format!(
         "/v1/intern/assets/{}/{}",
         pub_key.to_string(),
         query
@@ -189,3 +191,41 @@ fn intern_assets_endcoded_query() {
     assert_eq!(status, StatusCode::Ok);
     assert_eq!(response, Ok(AssetIdResponseBody { assets }));
 }
+
+#[test]
+fn intern_endpoints_only_on_private_api() {
+    let testkit = TestKit::default();
+    let api = testkit.api();
+    let (pub_key, _) = crypto::gen_keypair();
+
+    let endpoints = vec![
+        format!("v1/intern/assets/{}/asset", pub_key.to_string()),
+        "metrics".to_string(),
+        "db_stats".to_string(),
+    ];
+    for endpoint in endpoints {
+        let url = format!(
+            "http://localhost:3000/api/services/cryptocurrency/{}",
+            endpoint
+        );
+
+        let status = match request::get(&url, Headers::new(), api.public_handler()) {
+            Ok(response) => response.status,
+            Err(e) => e.response.status,
+        };
+        assert_eq!(status, Some(StatusCode::NotFound), "{}", endpoint);
+
+        let status = match request::get(&url, Headers::new(), api.private_handler()) {
+            Ok(response) => response.status,
+            Err(e) => e.response.status,
+        };
+        assert_ne!(status, Some(StatusCode::NotFound), "{}", endpoint);
+    }
+
+    let url = "http://localhost:3000/api/services/cryptocurrency/v1/intern/assets";
+    let status = match request::post(url, Headers::new(), "{}", api.public_handler()) {
+        Ok(response) => response.status,
+        Err(e) => e.response.status,
+    };
+    assert_eq!(status, Some(StatusCode::NotFound));
+}
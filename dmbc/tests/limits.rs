@@ -0,0 +1,144 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{chain, served};
+use hyper::header::{ContentLength, Headers};
+use hyper::status::StatusCode;
+use iron::prelude::*;
+use iron_test::{request, response};
+
+use dmbc::config::Rate;
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::limits::{BodyLimit, RateLimit};
+
+fn forwarded_for(addresses: &str) -> Headers {
+    let mut headers = Headers::new();
+    headers.set_raw("X-Forwarded-For", vec![addresses.as_bytes().to_vec()]);
+    headers
+}
+
+fn assert_rejected(response: Response, status: StatusCode, error: ApiError) {
+    assert_eq!(response.status, Some(status));
+    let body = response::extract_body_to_string(response);
+    let result: Result<(), ApiError> = serde_json::from_str(&body).unwrap();
+    assert_eq!(result, Err(error));
+}
+
+#[test]
+fn rate_limit_per_ip() {
+    let handler = chain(RateLimit::new(Some(Rate::new(1.0, 2)), vec![], vec![]));
+    let url = "http://localhost:3000/v1/blocks";
+
+    for _ in 0..2 {
+        let response = served(request::get(url, Headers::new(), &handler));
+        assert_eq!(response.status, Some(StatusCode::Ok));
+    }
+
+    let response = served(request::get(url, Headers::new(), &handler));
+    assert_eq!(
+        response.headers.get_raw("Retry-After"),
+        Some(&[b"1".to_vec()][..])
+    );
+    assert_rejected(response, StatusCode::TooManyRequests, ApiError::TooManyRequests);
+}
+
+#[test]
+fn rate_limit_per_route() {
+    let handler = chain(RateLimit::new(
+        None,
+        vec![("/v1/wallets".to_string(), Rate::new(1.0, 1))],
+        vec![],
+    ));
+
+    let url = "http://localhost:3000/v1/wallets";
+
+    let response = served(request::get(url, Headers::new(), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let response = served(request::get(url, Headers::new(), &handler));
+    assert_rejected(response, StatusCode::TooManyRequests, ApiError::TooManyRequests);
+
+    let url = "http://localhost:3000/v1/wallets/a0b1";
+    for _ in 0..3 {
+        let response = served(request::get(url, Headers::new(), &handler));
+        assert_eq!(response.status, Some(StatusCode::Ok));
+    }
+}
+
+#[test]
+fn rate_limit_fractional() {
+    let handler = chain(RateLimit::new(Some(Rate::new(0.5, 1)), vec![], vec![]));
+    let url = "http://localhost:3000/v1/blocks";
+
+    let response = served(request::get(url, Headers::new(), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let response = served(request::get(url, Headers::new(), &handler));
+    assert_eq!(
+        response.headers.get_raw("Retry-After"),
+        Some(&[b"2".to_vec()][..])
+    );
+    assert_rejected(response, StatusCode::TooManyRequests, ApiError::TooManyRequests);
+}
+
+#[test]
+fn rate_limit_forwarded_for() {
+    let url = "http://localhost:3000/v1/blocks";
+
+    // Requests come from 127.0.0.1, which is trusted here.
+    let proxy = "127.0.0.1".parse().unwrap();
+    let handler = chain(RateLimit::new(Some(Rate::new(1.0, 1)), vec![], vec![proxy]));
+
+    let response = served(request::get(url, forwarded_for("10.0.0.1"), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+    let response = served(request::get(url, forwarded_for("10.0.0.1"), &handler));
+    assert_rejected(response, StatusCode::TooManyRequests, ApiError::TooManyRequests);
+
+    // Trusted hops are skipped, and the address before them is the client.
+    let response = served(request::get(url, forwarded_for("10.0.0.2, 127.0.0.1"), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+    let response = served(request::get(url, forwarded_for("10.0.0.1, 10.0.0.2"), &handler));
+    assert_rejected(response, StatusCode::TooManyRequests, ApiError::TooManyRequests);
+
+    // Without the header the proxy itself is the client.
+    let response = served(request::get(url, Headers::new(), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    // The header of an untrusted peer is ignored.
+    let handler = chain(RateLimit::new(Some(Rate::new(1.0, 1)), vec![], vec![]));
+    let response = served(request::get(url, forwarded_for("10.0.0.1"), &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+    let response = served(request::get(url, forwarded_for("10.0.0.2"), &handler));
+    assert_rejected(response, StatusCode::TooManyRequests, ApiError::TooManyRequests);
+}
+
+#[test]
+fn body_limit() {
+    let handler = chain(BodyLimit::new(16));
+    let url = "http://localhost:3000/v1/transactions";
+    let headers = |length: usize| {
+        let mut headers = Headers::new();
+        headers.set(ContentLength(length as u64));
+        headers
+    };
+
+    let body = "{\"a\": \"0123456\"}";
+    let response = served(request::post(url, headers(body.len()), body, &handler));
+    assert_eq!(response.status, Some(StatusCode::Ok));
+
+    let body = "{\"a\": \"01234567\"}";
+    let response = served(request::post(url, headers(body.len()), body, &handler));
+    assert_rejected(
+        response,
+        StatusCode::PayloadTooLarge,
+        ApiError::RequestBodyTooLarge,
+    );
+}
@@ -0,0 +1,55 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate log;
+extern crate serde_json;
+
+use exonum::crypto;
+use log::{Level, Record};
+use serde_json::Value;
+
+use dmbc::currency::events::TxType;
+use dmbc::logging::{self, Context};
+
+fn record_json() -> Value {
+    let line = logging::json_record(
+        &Record::builder()
+            .args(format_args!("Executed"))
+            .level(Level::Info)
+            .module_path(Some("dmbc::currency"))
+            .build(),
+        &logging::context(),
+    );
+    serde_json::from_str(&line).unwrap()
+}
+
+#[test]
+fn logging_json_context() {
+    let tx_hash = crypto::hash(b"tx");
+    {
+        let _block = logging::enter(Context {
+            height: Some(5),
+            ..Default::default()
+        });
+        let _tx = logging::enter(Context {
+            tx_hash: Some(tx_hash),
+            tx_type: Some(TxType::Transfer),
+            result: Some("ok"),
+            ..Default::default()
+        });
+
+        let record = record_json();
+        assert_eq!(record["level"], "INFO");
+        assert_eq!(record["module"], "dmbc::currency");
+        assert_eq!(record["msg"], "Executed");
+        assert_eq!(record["height"], 5);
+        assert_eq!(record["tx_hash"], tx_hash.to_hex());
+        assert_eq!(record["tx_type"], "transfer");
+        assert_eq!(record["result"], "ok");
+        assert!(record["ts"].is_string());
+    }
+
+    let record = record_json();
+    assert_eq!(logging::context(), Context::default());
+    assert!(record.get("tx_hash").is_none());
+    assert!(record.get("height").is_none());
+}
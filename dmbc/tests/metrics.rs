@@ -0,0 +1,111 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate prometheus;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::crypto;
+
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+/// Value of the counter `name` with exactly the given labels.
+fn counter(name: &str, labels: &[(&str, &str)]) -> u64 {
+    prometheus::gather()
+        .iter()
+        .filter(|family| family.get_name() == name)
+        .flat_map(|family| family.get_metric().iter())
+        .find(|metric| {
+            labels.iter().all(|&(label, value)| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|pair| pair.get_name() == label && pair.get_value() == value)
+            })
+        })
+        .map_or(0, |metric| metric.get_counter().get_value() as u64)
+}
+
+#[test]
+fn metrics_by_type_and_result() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let permissions = TransactionPermissions::default();
+
+    let (rich_key, rich_secret) = crypto::gen_keypair();
+    let (poor_key, poor_secret) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_wallet_value(&rich_key, Wallet::new(100_000, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let paid = transaction::Builder::new()
+        .keypair(rich_key, rich_secret)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+    let unpaid = transaction::Builder::new()
+        .keypair(poor_key, poor_secret)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(2)
+        .build();
+
+    api.post_tx(&paid);
+    api.post_tx(&unpaid);
+    testkit.create_block();
+
+    let ok = [("type", "transfer"), ("result", "ok")];
+    let failed = [("type", "transfer"), ("result", "insufficient_funds")];
+    assert_eq!(counter("dmbc_transaction_execute_total", &ok), 1);
+    assert_eq!(counter("dmbc_transaction_execute_total", &failed), 1);
+    assert_eq!(
+        counter("dmbc_transaction_execute_started_total", &[("type", "transfer")]),
+        2
+    );
+}
+
+#[test]
+fn simulation_not_counted() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_delete_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_del_assets()
+        .add_asset("asset", 10)
+        .seed(3)
+        .build();
+
+    let type_label = [("type", "delete_assets")];
+    let started = counter("dmbc_transaction_execute_started_total", &type_label);
+    let executed = counter("dmbc_transaction_execute_total", &type_label);
+    let verified = counter("dmbc_transaction_verify_total", &type_label);
+
+    api.simulate_tx(&tx_delete_assets);
+
+    assert_eq!(
+        counter("dmbc_transaction_execute_started_total", &type_label),
+        started
+    );
+    assert_eq!(counter("dmbc_transaction_execute_total", &type_label), executed);
+    assert_eq!(counter("dmbc_transaction_verify_total", &type_label), verified);
+}
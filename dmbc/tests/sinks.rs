@@ -0,0 +1,171 @@
+extern crate dmbc;
+extern crate serde_json;
+
+use std::env;
+use std::fs;
+use std::io;
+
+use dmbc::config::Sink;
+use dmbc::currency::sinks::{EventSink, FileSink, Outbox};
+
+/// Sink that accepts a limited number of events and fails afterwards.
+struct FlakySink {
+    accept: usize,
+    received: Vec<String>,
+}
+
+impl EventSink for FlakySink {
+    fn name(&self) -> String {
+        "flaky".to_string()
+    }
+
+    fn publish(&mut self, _subject: &str, msg: &str) -> io::Result<()> {
+        if self.received.len() >= self.accept {
+            return Err(io::Error::new(io::ErrorKind::Other, "unavailable"));
+        }
+        self.received.push(msg.to_string());
+        Ok(())
+    }
+}
+
+#[test]
+fn file_sink_appends_lines() {
+    let path = env::temp_dir().join(format!("dmbc-file-sink-{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let mut sink = FileSink::new(path.to_str().unwrap().to_string());
+    sink.publish("transaction.commit", r#"{"a": 1}"#).unwrap();
+    sink.publish("transaction.commit", "{\n\"b\": 2\n}").unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    let lines = content.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    for line in lines {
+        assert!(serde_json::from_str::<serde_json::Value>(line).is_ok());
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn sink_config() {
+    let sinks: Vec<Sink> = serde_json::from_str(
+        r#"[
+            {"type": "file", "path": "./var/events.jsonl"},
+            {"type": "webhook", "url": "http://localhost:8080/events", "retries": 5}
+        ]"#,
+    ).unwrap();
+
+    assert_eq!(
+        sinks,
+        vec![
+            Sink::File {
+                path: "./var/events.jsonl".to_string(),
+            },
+            Sink::Webhook {
+                url: "http://localhost:8080/events".to_string(),
+                retries: Some(5),
+            },
+        ]
+    );
+}
+
+#[test]
+fn outbox_retries_in_order() {
+    let path = env::temp_dir().join(format!("dmbc-outbox-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+
+    {
+        let outbox = Outbox::open(&path).unwrap();
+        assert_eq!(outbox.push("subject", r#"{"tx": 0}"#).unwrap(), 0);
+        assert_eq!(outbox.push("subject", r#"{"tx": 1}"#).unwrap(), 1);
+        assert_eq!(outbox.push("subject", r#"{"tx": 2}"#).unwrap(), 2);
+
+        let mut sink = FlakySink {
+            accept: 1,
+            received: vec![],
+        };
+        assert!(outbox.deliver(&mut sink).is_err());
+        assert_eq!(outbox.len(), 2);
+        assert_eq!(sink.received, vec![r#"{"seq":0,"tx":0}"#.to_string()]);
+    }
+
+    // undelivered events and the sequence survive a restart
+    let outbox = Outbox::open(&path).unwrap();
+    assert_eq!(outbox.len(), 2);
+    assert_eq!(outbox.push("subject", "plain").unwrap(), 3);
+
+    let mut sink = FlakySink {
+        accept: 10,
+        received: vec![],
+    };
+    assert_eq!(outbox.deliver(&mut sink).unwrap(), 3);
+    assert!(outbox.is_empty());
+    assert_eq!(
+        sink.received,
+        vec![
+            r#"{"seq":1,"tx":1}"#.to_string(),
+            r#"{"seq":2,"tx":2}"#.to_string(),
+            r#"{"data":"plain","seq":3}"#.to_string(),
+        ]
+    );
+
+    drop(outbox);
+    fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn outbox_clones_share_queue() {
+    let path = env::temp_dir().join(format!("dmbc-outbox-shared-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+
+    let outbox = Outbox::open(&path).unwrap();
+    let delivery = outbox.clone();
+    let pusher = {
+        let outbox = outbox.clone();
+        std::thread::spawn(move || {
+            for i in 0..10 {
+                outbox.push("subject", &format!(r#"{{"tx": {}}}"#, i)).unwrap();
+            }
+        })
+    };
+    pusher.join().unwrap();
+    assert_eq!(delivery.len(), 10);
+
+    let mut sink = FlakySink {
+        accept: 10,
+        received: vec![],
+    };
+    assert_eq!(delivery.deliver(&mut sink).unwrap(), 10);
+    assert!(outbox.is_empty());
+    assert_eq!(sink.received[9], r#"{"seq":9,"tx":9}"#.to_string());
+
+    drop(outbox);
+    drop(delivery);
+    fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn outbox_pushes_batches() {
+    let path = env::temp_dir().join(format!("dmbc-outbox-batch-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&path);
+
+    let outbox = Outbox::open(&path).unwrap();
+    assert_eq!(outbox.push("subject", r#"{"tx": 0}"#).unwrap(), 0);
+    let events = (1..4)
+        .map(|i| ("subject".to_string(), format!(r#"{{"tx": {}}}"#, i)))
+        .collect::<Vec<_>>();
+    assert_eq!(outbox.push_all(&events).unwrap(), 1..4);
+    assert_eq!(outbox.push_all(&[]).unwrap(), 4..4);
+    assert_eq!(outbox.len(), 4);
+
+    let mut sink = FlakySink {
+        accept: 10,
+        received: vec![],
+    };
+    assert_eq!(outbox.deliver(&mut sink).unwrap(), 4);
+    assert_eq!(sink.received[3], r#"{"seq":3,"tx":3}"#.to_string());
+
+    drop(outbox);
+    fs::remove_dir_all(&path).unwrap();
+}
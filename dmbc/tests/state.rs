@@ -0,0 +1,120 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+
+use std::io::Cursor;
+
+use exonum::crypto;
+use exonum_testkit::TestKitBuilder;
+
+use dmbc::currency;
+use dmbc::currency::assets::{self, AssetId};
+use dmbc::currency::error::Error;
+use dmbc::currency::genesis::{Genesis, GenesisAsset, GenesisAssetFees, GenesisWallet};
+use dmbc::currency::offers::{self, history, HistoryOffer, HistoryOffers, Offer, OpenOffers};
+use dmbc::currency::state::{self, Dump, Record};
+use dmbc::currency::wallet::Wallet;
+use dmbc::currency::{status, supply, wallet};
+
+#[test]
+fn state_export_and_seed() {
+    let (holder, _) = crypto::gen_keypair();
+    let mut source = Genesis::default();
+    source.wallets.push(GenesisWallet {
+        pub_key: holder,
+        balance: 500,
+        assets: vec![GenesisAsset {
+            data: "gold".to_string(),
+            amount: 10,
+            creator: None,
+            fees: GenesisAssetFees::default(),
+        }],
+    });
+    let id = AssetId::from_data("gold", &holder);
+    let tx_hash = crypto::hash(b"ask");
+
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(1)
+        .with_service(currency::Service::with_genesis(source))
+        .create();
+    {
+        let blockchain = testkit.blockchain_mut();
+        let mut fork = blockchain.fork();
+        // The ask escrows 6 coins of the holder.
+        let holder_wallet = wallet::Schema(&fork).fetch(&holder);
+        let escrowed = Wallet::new(holder_wallet.balance() - 6, holder_wallet.assets());
+        wallet::Schema(&mut fork).store(&holder, escrowed);
+        let mut open_offers = OpenOffers::new_open_offers();
+        open_offers.add_ask(3, Offer::new(&holder, 2, &tx_hash));
+        offers::Schema(&mut fork).store(&id, open_offers);
+        let fills = HistoryOffers::new(vec![HistoryOffer::new(&crypto::hash(b"bid"), 1)]);
+        history::Schema(&mut fork).store(&tx_hash, fills);
+        status::Schema(&mut fork).store(tx_hash, Err(Error::InsufficientFunds));
+        assert!(blockchain.merge(fork.into_patch()).is_ok());
+    }
+    testkit.create_block();
+
+    let mut out = Vec::new();
+    let snapshot = testkit.blockchain_mut().snapshot();
+    let header = state::export(snapshot.as_ref(), &mut out).unwrap();
+    assert_eq!(header.height, 1);
+    assert_eq!(header.block_hash, testkit.blockchain_mut().last_hash());
+    assert_eq!(header.supply, supply::GENESIS_SUPPLY + 500);
+
+    let dump = Dump::read(Cursor::new(&out)).unwrap();
+    assert_eq!(dump.header, header);
+    assert!(dump.records.iter().any(|r| match *r {
+        Record::Status { code, .. } => code == Error::InsufficientFunds as u8,
+        _ => false,
+    }));
+
+    assert!(dump.validate(&Genesis::default()).is_err());
+    let mut target = Genesis::default();
+    target.wallets.clear();
+    assert_eq!(dump.validate(&target), Ok(()));
+
+    let mut inflated = dump.clone();
+    inflated.header.supply += 1;
+    assert!(inflated.validate(&target).is_err());
+
+    let mut crowded = target.clone();
+    crowded.wallets.push(GenesisWallet {
+        pub_key: crypto::gen_keypair().0,
+        balance: u64::max_value() - 100,
+        assets: Vec::new(),
+    });
+    let errors = dump.validate(&crowded).unwrap_err();
+    assert!(errors.iter().any(|e| e.contains("overflows")));
+
+    let mut seeded = TestKitBuilder::validator()
+        .with_validators(1)
+        .with_service(currency::Service::with_genesis(target).with_state(dump))
+        .create();
+    let fork = seeded.blockchain_mut().fork();
+
+    let wallet = wallet::Schema(&fork).fetch(&holder);
+    assert_eq!(wallet.balance(), 494);
+    assert_eq!(wallet.assets()[0].id(), id);
+    assert_eq!(assets::Schema(&fork).fetch(&id).unwrap().amount(), 10);
+    assert_eq!(offers::Schema(&fork).fetch(&id).asks()[0].price(), 3);
+    assert_eq!(history::Schema(&fork).fetch(&tx_hash).history().len(), 1);
+    assert_eq!(
+        status::Schema(&fork).fetch(&tx_hash),
+        Some(Err(Error::InsufficientFunds))
+    );
+    assert_eq!(supply::Schema(&fork).fetch(), supply::GENESIS_SUPPLY + 500);
+}
+
+#[test]
+fn state_read_errors() {
+    let no_header = r#"{"type":"status","tx_hash":"00","code":0}"#;
+    assert!(Dump::read(Cursor::new(no_header)).is_err());
+    assert!(Dump::read(Cursor::new("")).is_err());
+
+    let hash = crypto::hash(b"block").to_hex();
+    let future = format!(
+        r#"{{"type":"header","version":99,"height":0,"block_hash":"{}","supply":0}}"#,
+        hash
+    );
+    assert!(Dump::read(Cursor::new(future)).is_err());
+}
@@ -0,0 +1,97 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::crypto;
+use hyper::status::StatusCode;
+
+use dmbc::currency::api::supply::SupplyResponse;
+use dmbc::currency::supply::{self, OverflowError, Supply, GENESIS_SUPPLY};
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn supply_genesis() {
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    testkit.create_block();
+
+    let (status, response): (StatusCode, SupplyResponse) = api.get_with_status("/v1/supply");
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap();
+    assert_eq!(body.supply.total, GENESIS_SUPPLY);
+    assert_eq!(body.supply.wallets, GENESIS_SUPPLY);
+    assert_eq!(body.supply.escrowed, 0);
+    assert!(body.conserved);
+}
+
+#[test]
+fn supply_includes_minted_wallets() {
+    let balance = 100_000;
+    let (public_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    testkit.create_block();
+
+    let (status, response): (StatusCode, SupplyResponse) = api.get_with_status("/v1/supply");
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap();
+    assert_eq!(body.supply.total, GENESIS_SUPPLY + balance);
+    assert!(body.conserved);
+}
+
+#[test]
+fn supply_discrepancy() {
+    let supply = Supply {
+        total: 10,
+        wallets: 7,
+        escrowed: 2,
+    };
+    assert!(!supply.is_conserved());
+    assert_eq!(supply.discrepancy(), 1);
+
+    let supply = Supply {
+        total: 10,
+        wallets: 10,
+        escrowed: 2,
+    };
+    assert_eq!(supply.discrepancy(), -2);
+
+    // Counts that overflowed in the audit are reported at the maximum.
+    let supply = Supply {
+        total: 10,
+        wallets: u64::max_value(),
+        escrowed: u64::max_value(),
+    };
+    assert!(!supply.is_conserved());
+    assert_eq!(supply.discrepancy(), -i64::max_value());
+}
+
+#[test]
+fn supply_mint_overflow() {
+    let mut testkit = DmbcTestApiBuilder::new().create();
+    let mut fork = testkit.blockchain_mut().fork();
+
+    assert_eq!(supply::Schema(&mut fork).mint(10), Ok(()));
+    assert_eq!(supply::Schema(&fork).fetch(), GENESIS_SUPPLY + 10);
+
+    assert_eq!(
+        supply::Schema(&mut fork).mint(u64::max_value()),
+        Err(OverflowError)
+    );
+    assert_eq!(supply::Schema(&fork).fetch(), GENESIS_SUPPLY + 10);
+}
@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -60,6 +60,7 @@ fn add_assets_mine_new_asset_to_receiver_empty_wallet() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -141,6 +142,7 @@ fn add_assets_mine_existing_asset_to_receivers_non_empty_wallet() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -222,6 +224,7 @@ fn add_assets_mine_existing_asset_to_creators_empty_wallet() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -312,6 +315,7 @@ fn add_assets_mine_existing_asset_to_creator_and_receiver() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -397,6 +401,7 @@ fn add_assets_mine_existing_asset_to_receivers_wallet_with_different_asset() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -476,6 +481,7 @@ fn add_assets_mine_existing_asset_with_different_fees() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -530,6 +536,7 @@ fn add_assets_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -581,6 +588,7 @@ fn add_assets_transaction_too_large() {
 
     let (status, response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
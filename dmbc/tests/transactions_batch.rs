@@ -0,0 +1,135 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
+use exonum::crypto;
+use exonum::messages::Message;
+use hyper::status::StatusCode;
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::{TransactionResponse, TxBatchPostResponse};
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn batch_transfers() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let permissions = TransactionPermissions::default();
+    let balance = 100_000;
+    let amount = 100;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let transfers = (0..3)
+        .map(|seed| {
+            transaction::Builder::new()
+                .keypair(public_key, secret_key.clone())
+                .tx_transfer()
+                .amount(amount)
+                .recipient(recipient_key)
+                .seed(seed)
+                .build()
+        })
+        .collect::<Vec<_>>();
+
+    let batch = transfers
+        .iter()
+        .map(|tx| serde_json::to_value(tx).unwrap())
+        .collect::<Vec<_>>();
+
+    let (status, response): (StatusCode, TxBatchPostResponse) =
+        api.post_with_status("/v1/transactions/batch", &batch);
+    testkit.create_block();
+    testkit.assert_supply_conserved();
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(transfers
+            .iter()
+            .map(|tx| Ok(TransactionResponse { tx_hash: tx.hash() }))
+            .collect())
+    );
+
+    for tx in &transfers {
+        let (_, tx_status) = api.get_tx_status(tx);
+        assert_eq!(tx_status, Ok(Ok(())));
+    }
+
+    let recipient_wallet = api.get_wallet(&recipient_key);
+    assert_eq!(recipient_wallet.balance, amount * 3);
+}
+
+#[test]
+fn batch_invalid_item() {
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(1)
+        .build();
+
+    let batch = vec![
+        serde_json::from_str::<serde_json::Value>(r#"{"body": "garbage"}"#).unwrap(),
+        serde_json::to_value(&tx_transfer).unwrap(),
+    ];
+
+    let (status, response): (StatusCode, TxBatchPostResponse) =
+        api.post_with_status("/v1/transactions/batch", &batch);
+    testkit.create_block();
+
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(
+        response,
+        Ok(vec![
+            Err(Error::InvalidTransaction),
+            Ok(TransactionResponse {
+                tx_hash: tx_transfer.hash(),
+            }),
+        ])
+    );
+
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Ok(Ok(())));
+}
+
+#[test]
+fn batch_not_an_array() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, TxBatchPostResponse) =
+        api.post_raw_with_status("/v1/transactions/batch", r#"{"body": "garbage"}"#);
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::IncorrectRequest));
+}
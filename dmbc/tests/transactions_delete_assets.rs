@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -58,6 +58,7 @@ fn delete_assets_one_from_bundle() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -122,6 +123,7 @@ fn delete_assets_all_from_bundle() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -164,6 +166,7 @@ fn delete_assets_that_doent_exist() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -214,6 +217,7 @@ fn delete_assets_that_doent_exist2() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -276,6 +280,7 @@ fn delete_assets_amount_more_than_wallet_have() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -337,6 +342,7 @@ fn delete_assets_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -398,6 +404,7 @@ fn delete_assets_with_different_creator() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -468,6 +475,7 @@ fn delete_assets_two_assets_where_one_asset_doesnt_have_enough_items() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -539,6 +547,7 @@ fn delete_assets_two_assets_where_one_have_another_creator() {
 
     let (status, response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
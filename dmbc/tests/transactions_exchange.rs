@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -116,6 +116,7 @@ fn exchange_assets_fee_from_recipient() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -271,6 +272,7 @@ fn exchange_assets_fee_from_sender() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -426,6 +428,7 @@ fn exchange_assets_fee_from_recipient_and_sender() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -578,6 +581,7 @@ fn exchange_assets_invalid_tx() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -693,6 +697,7 @@ fn exchange_assets_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -808,6 +813,7 @@ fn exchange_assets_assets_not_found() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -925,6 +931,7 @@ fn exchange_assets_insufficient_assets() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -119,6 +119,7 @@ fn exchange_intermediary_assets() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -243,6 +244,7 @@ fn exchange_intermediary_assets_creator_is_sender() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -356,6 +358,7 @@ fn exchange_intermediary_assets_creator_is_intermediary() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -468,6 +471,7 @@ fn exchange_intermediary_assets_payer_fee_intermediary() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -576,6 +580,7 @@ fn exchange_intermediary_assets_asset_not_found() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -659,6 +664,7 @@ fn exchange_intermediary_assets_insufficient_assets() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -742,6 +748,7 @@ fn exchange_intermediary_assets_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -0,0 +1,190 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate futures;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate router;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::api::Api;
+use exonum::blockchain::{self, Blockchain, Transaction};
+use exonum::crypto;
+use exonum::messages::Message;
+use exonum::node::ApiSender;
+use exonum::storage::MemoryDB;
+use futures::sync::mpsc;
+use hyper::header::Headers;
+use hyper::status::StatusCode;
+use iron_test::{request, response};
+use router::Router;
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::api::transaction::{TransactionApi, TransactionInfoResponse,
+                                       TransactionLocation, TransactionState};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+use dmbc::currency::Service;
+
+#[test]
+fn lookup_committed() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (status, response) = api.get_tx_info(&tx_transfer);
+
+    assert_eq!(status, StatusCode::Ok);
+    let info = response.unwrap();
+    assert_eq!(info.state, TransactionState::Committed);
+    assert_eq!(
+        info.location,
+        Some(TransactionLocation {
+            height: 1,
+            index: 0,
+        })
+    );
+    assert_eq!(info.content, Some(serde_json::to_value(&tx_transfer).unwrap()));
+    assert_eq!(info.status, Some(Ok(())));
+}
+
+#[test]
+fn lookup_committed_failed() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(10, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(100)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    api.post_tx(&tx_transfer);
+    testkit.create_block();
+
+    let (status, response) = api.get_tx_info(&tx_transfer);
+
+    assert_eq!(status, StatusCode::Ok);
+    let info = response.unwrap();
+    assert_eq!(info.state, TransactionState::Committed);
+    assert_eq!(info.status, Some(Err(Error::InsufficientFunds)));
+}
+
+#[test]
+fn lookup_in_mempool() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    // The API is given a pool holding the transaction, as the node's does
+    // until the transaction is committed.
+    let mut pool: BTreeMap<_, Box<Transaction>> = BTreeMap::new();
+    pool.insert(tx_transfer.hash(), Box::new(tx_transfer.clone()));
+
+    let (service_key, service_secret_key) = crypto::gen_keypair();
+    let services: Vec<Box<blockchain::Service>> = vec![Box::new(Service::new())];
+    let api = TransactionApi {
+        channel: ApiSender::new(mpsc::channel(1).0),
+        blockchain: Blockchain::new(
+            Box::new(MemoryDB::new()),
+            services,
+            service_key,
+            service_secret_key,
+            ApiSender::new(mpsc::channel(1).0),
+        ),
+        tx_pool: Some(Arc::new(RwLock::new(pool))),
+    };
+    let mut router = Router::new();
+    api.wire(&mut router);
+
+    let url = format!(
+        "http://localhost:3000/v1/transactions/{}",
+        tx_transfer.hash().to_string()
+    );
+    let response = request::get(&url, Headers::new(), &router).unwrap();
+
+    assert_eq!(response.status, Some(StatusCode::Ok));
+    let body = response::extract_body_to_string(response);
+    let response: TransactionInfoResponse = serde_json::from_str(&body).unwrap();
+    let info = response.unwrap();
+    assert_eq!(info.state, TransactionState::InMempool);
+    assert_eq!(info.location, None);
+    assert_eq!(info.content, Some(serde_json::to_value(&tx_transfer).unwrap()));
+    assert_eq!(info.status, None);
+}
+
+#[test]
+fn lookup_unknown() {
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let (status, response) = api.get_tx_info(&tx_transfer);
+
+    assert_eq!(status, StatusCode::NotFound);
+    let info = response.unwrap();
+    assert_eq!(info.state, TransactionState::Unknown);
+    assert_eq!(info.location, None);
+    assert_eq!(info.content, None);
+    assert_eq!(info.status, None);
+}
+
+#[test]
+fn lookup_invalid_hash() {
+    let testkit = DmbcTestApiBuilder::new().create();
+    let api = testkit.api();
+
+    let (status, response): (StatusCode, TransactionInfoResponse) =
+        api.get_with_status("/v1/transactions/not-a-hash");
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::TransactionHashInvalid));
+}
@@ -12,7 +12,7 @@ pub mod dmbc_testkit;
 use hyper::status::StatusCode;
 use exonum::messages::Message;
 use exonum::crypto;
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::transactions::builders::transaction;
@@ -52,6 +52,7 @@ fn set_3_bid_1_ask_result_1_bid_1_ask() {
 
     let (status, _) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let seller_assets = api
         .get_wallet_assets(&user1_pk)
@@ -78,6 +79,7 @@ fn set_3_bid_1_ask_result_1_bid_1_ask() {
 
         let (status, _) = api.post_tx(&tx_bid_offer);
         testkit.create_block();
+        testkit.assert_supply_conserved();
         assert_eq!(status, StatusCode::Created);
         let offer = Offer::new(&user1_pk, bid_amount, &tx_bid_offer.hash());
         sample_offers.add_bid(bid_price, offer);
@@ -103,6 +105,7 @@ fn set_3_bid_1_ask_result_1_bid_1_ask() {
 
     let (status, _) = api.post_tx(&tx_ask_offer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let _close_bids = sample_offers.close_bid(ask_price, ask_amount);
     let offer = Offer::new(&user2_pk, 1, &tx_ask_offer.hash());
@@ -166,6 +169,7 @@ fn set_3_ask_1_bid_result_2_ask_1_bid() {
 
     let (status, _) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let seller_assets = api
         .get_wallet_assets(&user1_pk)
@@ -191,6 +195,7 @@ fn set_3_ask_1_bid_result_2_ask_1_bid() {
 
         let (status, _) = api.post_tx(&tx_ask_offer);
         testkit.create_block();
+        testkit.assert_supply_conserved();
         assert_eq!(status, StatusCode::Created);
         let offer = Offer::new(&user2_pk, ask_amount, &tx_ask_offer.hash());
         sample_offers.add_ask(ask_price, offer);
@@ -213,6 +218,7 @@ fn set_3_ask_1_bid_result_2_ask_1_bid() {
 
     let (status, _) = api.post_tx(&tx_bid_offer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let _close_bids = sample_offers.close_ask(bid_price, bid_amount);
     let offer = Offer::new(&user1_pk, bid_amount - 2 , &tx_bid_offer.hash());
@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -63,6 +63,7 @@ fn add_assets_wallet_permissions() {
 
     let (status, _response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -105,6 +106,7 @@ fn add_assets_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -150,6 +152,7 @@ fn delete_assets_wallet_permissions() {
 
     let (status, _response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -192,6 +195,7 @@ fn delete_assets_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_delete_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -290,6 +294,7 @@ fn exchange_assets_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -389,6 +394,7 @@ fn exchange_assets_sender_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -488,6 +494,7 @@ fn exchange_assets_recipient_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -589,6 +596,7 @@ fn exchange_intermediary_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -691,6 +699,7 @@ fn exchange_intermediary_sender_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -793,6 +802,7 @@ fn exchange_intermediary_recipient_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -895,6 +905,7 @@ fn exchange_intermediary_intermediary_permissions() {
 
     let (status, _response) = api.post_tx(&tx_exchange_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -945,6 +956,7 @@ fn trade_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -996,6 +1008,7 @@ fn trade_seller_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1047,6 +1060,7 @@ fn trade_buyer_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1101,6 +1115,7 @@ fn trade_intermediary_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1157,6 +1172,7 @@ fn trade_intermediary_seller_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1213,6 +1229,7 @@ fn trade_intermediary_buyer_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1269,6 +1286,7 @@ fn trade_intermediary_intermediary_permissions() {
 
     let (status, _response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1315,6 +1333,7 @@ fn transfer_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1362,6 +1381,7 @@ fn transfer_sender_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1409,6 +1429,7 @@ fn transfer_recipient_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1455,6 +1476,7 @@ fn transfer_with_fees_payer_global_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1503,6 +1525,7 @@ fn transfer_with_fees_payer_sender_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1551,6 +1574,7 @@ fn transfer_with_fees_payer_recipient_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1599,6 +1623,7 @@ fn transfer_with_fees_payer_and_his_permissions() {
 
     let (status, _response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -1637,6 +1662,7 @@ fn bid_global_permissions() {
 
     let (status, _) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let seller_assets = api
         .get_wallet_assets(&user1_pk)
@@ -1663,6 +1689,7 @@ fn bid_global_permissions() {
 
         let (status, _) = api.post_tx(&tx_bid_offer);
         testkit.create_block();
+        testkit.assert_supply_conserved();
         assert_eq!(status, StatusCode::BadRequest);
     }
 }
@@ -1701,6 +1728,7 @@ fn bid_wallet_permissions() {
 
     let (status, _) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let seller_assets = api
         .get_wallet_assets(&user1_pk)
@@ -1727,6 +1755,7 @@ fn bid_wallet_permissions() {
 
         let (status, _) = api.post_tx(&tx_bid_offer);
         testkit.create_block();
+        testkit.assert_supply_conserved();
         assert_eq!(status, StatusCode::BadRequest);
     }
 }
@@ -1764,6 +1793,7 @@ fn ask_global_permissions() {
 
     let (status, _) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let seller_assets = api
         .get_wallet_assets(&user1_pk)
@@ -1788,6 +1818,7 @@ fn ask_global_permissions() {
 
         let (status, _) = api.post_tx(&tx_ask_offer);
         testkit.create_block();
+        testkit.assert_supply_conserved();
         assert_eq!(status, StatusCode::BadRequest);
     }
 }
@@ -1825,6 +1856,7 @@ fn ask_user_permissions() {
 
     let (status, _) = api.post_tx(&tx_add_assets);
     testkit.create_block();
+    testkit.assert_supply_conserved();
     assert_eq!(status, StatusCode::Created);
     let seller_assets = api
         .get_wallet_assets(&user1_pk)
@@ -1849,6 +1881,7 @@ fn ask_user_permissions() {
 
         let (status, _) = api.post_tx(&tx_ask_offer);
         testkit.create_block();
+        testkit.assert_supply_conserved();
         assert_eq!(status, StatusCode::BadRequest);
     }
 }
\ No newline at end of file
@@ -0,0 +1,190 @@
+extern crate dmbc;
+extern crate exonum;
+extern crate exonum_testkit;
+extern crate hyper;
+extern crate iron;
+extern crate iron_test;
+extern crate mount;
+extern crate serde_json;
+
+pub mod dmbc_testkit;
+
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use exonum::crypto;
+use exonum::messages::Message;
+use hyper::status::StatusCode;
+
+use dmbc::currency::api::error::ApiError;
+use dmbc::currency::assets::{AssetBundle, MetaAsset, TradeAsset};
+use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
+use dmbc::currency::error::Error;
+use dmbc::currency::transactions::builders::transaction;
+use dmbc::currency::wallet::Wallet;
+
+#[test]
+fn simulate_transfer() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, 0, transaction_fee);
+    let permissions = TransactionPermissions::default();
+    let balance = 100_000;
+    let amount = 5_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_transfer()
+        .amount(amount)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let (status, response) = api.simulate_tx(&tx_transfer);
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap().unwrap();
+    assert_eq!(body.tx_hash, tx_transfer.hash());
+    assert_eq!(body.status, Ok(()));
+    assert!(body.assets.is_empty());
+    assert!(body.offers.is_empty());
+
+    let sender = body.wallets.iter().find(|c| c.key == public_key).unwrap();
+    assert_eq!(sender.before, Some(Wallet::new(balance, vec![])));
+    assert_eq!(
+        sender.after,
+        Some(Wallet::new(balance - amount - transaction_fee, vec![]))
+    );
+
+    let recipient = body.wallets.iter().find(|c| c.key == recipient_key).unwrap();
+    assert_eq!(recipient.before, None);
+    assert_eq!(recipient.after, Some(Wallet::new(amount, vec![])));
+
+    // nothing is broadcast or stored
+    let (_, tx_status) = api.get_tx_status(&tx_transfer);
+    assert_eq!(tx_status, Err(ApiError::TransactionNotFound));
+    assert_eq!(api.get_wallet(&recipient_key).balance, 0);
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+}
+
+#[test]
+fn simulate_trade_insufficient_funds() {
+    let transaction_fee = 1000;
+    let config_fees = TransactionFees::with_default_key(0, 0, 0, 0, transaction_fee, 0);
+    let permissions = TransactionPermissions::default();
+    let units = 3;
+    let price = 500;
+
+    let (seller_public_key, seller_secret_key) = crypto::gen_keypair();
+    let (buyer_public_key, buyer_secret_key) = crypto::gen_keypair();
+
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        units,
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &seller_public_key,
+    );
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_wallet_value(&buyer_public_key, Wallet::new(100, vec![]))
+        .add_wallet_value(&seller_public_key, Wallet::new(100_000, vec![]))
+        .add_asset_to_wallet(&seller_public_key, (asset.clone(), info))
+        .create();
+    let api = testkit.api();
+
+    let tx_trade = transaction::Builder::new()
+        .keypair(buyer_public_key, buyer_secret_key)
+        .tx_trade_assets()
+        .add_asset_value(TradeAsset::from_bundle(asset.clone(), price))
+        .seller(seller_public_key, seller_secret_key)
+        .seed(1)
+        .build();
+
+    let (status, response) = api.simulate_tx(&tx_trade);
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap().unwrap();
+    assert_eq!(body.status, Err(Error::InsufficientFunds));
+
+    let seller_assets = api
+        .get_wallet_assets(&seller_public_key)
+        .iter()
+        .map(|a| a.into())
+        .collect::<Vec<AssetBundle>>();
+    assert_eq!(seller_assets, vec![asset]);
+}
+
+#[test]
+fn simulate_invalid_signature() {
+    let (public_key, _) = crypto::gen_keypair();
+    let (_, other_secret_key) = crypto::gen_keypair();
+    let (recipient_key, _) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&public_key, Wallet::new(100_000, vec![]))
+        .create();
+    let api = testkit.api();
+
+    let tx_transfer = transaction::Builder::new()
+        .keypair(public_key, other_secret_key)
+        .tx_transfer()
+        .amount(10)
+        .recipient(recipient_key)
+        .seed(42)
+        .build();
+
+    let (status, response) = api.simulate_tx(&tx_transfer);
+
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Ok(Err(Error::InvalidTransaction)));
+}
+
+#[test]
+fn simulate_panicking_transaction() {
+    let per_asset_fee = 2;
+    let config_fees = TransactionFees::with_default_key(0, per_asset_fee, 0, 0, 0, 0);
+    let permissions = TransactionPermissions::default();
+    let balance = 100_000;
+
+    let (public_key, secret_key) = crypto::gen_keypair();
+
+    let testkit = DmbcTestApiBuilder::new()
+        .with_configuration(Configuration::new(config_fees, permissions))
+        .add_wallet_value(&public_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+
+    // The fee for that many units overflows, which panics in the debug
+    // builds tests run with.
+    let meta_asset = MetaAsset::new(
+        &public_key,
+        "asset",
+        u64::max_value(),
+        dmbc_testkit::asset_fees(0, "0.0".parse().unwrap()),
+    );
+    let tx_add_assets = transaction::Builder::new()
+        .keypair(public_key, secret_key)
+        .tx_add_assets()
+        .add_asset_value(meta_asset)
+        .seed(85)
+        .build();
+
+    let (status, response) = api.simulate_tx(&tx_add_assets);
+
+    assert_eq!(status, StatusCode::Ok);
+    let body = response.unwrap().unwrap();
+    assert_eq!(body.tx_hash, tx_add_assets.hash());
+    assert_eq!(body.status, Err(Error::UnableToVerifyTransaction));
+    assert!(body.wallets.is_empty());
+    assert!(body.assets.is_empty());
+    assert!(body.offers.is_empty());
+
+    assert_eq!(api.get_wallet(&public_key).balance, balance);
+}
@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -67,6 +67,7 @@ fn trade_fee_from_recipient() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -142,6 +143,7 @@ fn trade_fee_from_sender() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -216,6 +218,7 @@ fn trade_fee_from_recipient_and_sender() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -288,6 +291,7 @@ fn trade_fee_bad_request() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::BadRequest);
@@ -354,6 +358,7 @@ fn trade_asset_not_found() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -420,6 +425,7 @@ fn trade_insufficient_assets() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -486,6 +492,7 @@ fn trade_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
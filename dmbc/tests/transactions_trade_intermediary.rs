@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -71,6 +71,7 @@ fn trade_intermediary_fee_from_recipient() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -154,6 +155,7 @@ fn trade_intermediary_fee_from_sender() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -236,6 +238,7 @@ fn trade_intermediary_fee_from_recipient_and_sender() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -323,6 +326,7 @@ fn trade_intermediary_fee_from_intermediary() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -406,6 +410,7 @@ fn trade_intermediary_asset_not_found() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -481,6 +486,7 @@ fn trade_intermediary_insufficient_assets() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -556,6 +562,7 @@ fn trade_intermediary_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_trade);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
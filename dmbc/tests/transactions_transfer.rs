@@ -9,7 +9,7 @@ extern crate serde_json;
 
 pub mod dmbc_testkit;
 
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 use exonum::crypto;
 use exonum::messages::Message;
 use hyper::status::StatusCode;
@@ -60,6 +60,7 @@ fn transfer() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -126,6 +127,7 @@ fn transfer_asset_not_found() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -187,6 +189,7 @@ fn transfer_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -245,6 +248,7 @@ fn transfer_insufficient_assets() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
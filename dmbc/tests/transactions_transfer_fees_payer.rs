@@ -12,7 +12,7 @@ pub mod dmbc_testkit;
 use hyper::status::StatusCode;
 use exonum::messages::Message;
 use exonum::crypto;
-use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKitApi};
+use dmbc_testkit::{DmbcTestApiBuilder, DmbcTestKit, DmbcTestKitApi};
 
 use dmbc::currency::configuration::{Configuration, TransactionFees, TransactionPermissions};
 use dmbc::currency::transactions::builders::transaction;
@@ -61,6 +61,7 @@ fn transfer_with_fees_payer() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -123,6 +124,7 @@ fn transfer_with_fees_payer_asset_not_found() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -184,6 +186,7 @@ fn transfer_with_fees_payer_insufficient_funds() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
@@ -236,6 +239,7 @@ fn transfer_with_fees_payer_insufficient_assets() {
 
     let (status, response) = api.post_tx(&tx_transfer);
     testkit.create_block();
+    testkit.assert_supply_conserved();
 
     // check post response
     assert_eq!(status, StatusCode::Created);
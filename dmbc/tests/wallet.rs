@@ -586,3 +586,56 @@ fn wallet_asset_whit_info() {
     assert_eq!(status, StatusCode::Ok);
     assert_eq!(response, Ok(extended_asset));
 }
+
+#[test]
+fn wallet_at_height() {
+    let balance = 1000;
+    let (pub_key, _) = crypto::gen_keypair();
+    let (asset, info) = dmbc_testkit::create_asset(
+        "asset",
+        2,
+        dmbc_testkit::asset_fees(10, "0.0".parse().unwrap()),
+        &pub_key,
+    );
+
+    let mut testkit = DmbcTestApiBuilder::new()
+        .add_wallet_value(&pub_key, Wallet::new(balance, vec![]))
+        .create();
+    let api = testkit.api();
+    testkit.create_block();
+
+    testkit.store_wallet(&pub_key, Wallet::new(500, vec![]));
+    testkit.add_assets(&pub_key, vec![(asset.clone(), info)]);
+    testkit.create_block();
+
+    let wallet_at = |height: u64| -> WalletResponse {
+        api.get_with_status(&format!("/v1/wallets/{}?height={}", pub_key.to_string(), height))
+            .1
+    };
+    assert_eq!(wallet_at(0), Ok(WalletInfo::from(Wallet::new_empty())));
+    assert_eq!(wallet_at(1), Ok(WalletInfo::from(Wallet::new(balance, vec![]))));
+    assert_eq!(
+        wallet_at(2),
+        Ok(WalletInfo::from(Wallet::new(balance + 500, vec![asset.clone()])))
+    );
+
+    let (status, response): (StatusCode, WalletResponse) =
+        api.get_with_status(&format!("/v1/wallets/{}?height=3", pub_key.to_string()));
+    assert_eq!(status, StatusCode::NotFound);
+    assert_eq!(response, Err(ApiError::BlockNotFound));
+
+    let (status, response): (StatusCode, WalletResponse) =
+        api.get_with_status(&format!("/v1/wallets/{}?height=last", pub_key.to_string()));
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(response, Err(ApiError::IncorrectRequest));
+
+    let (status, response): (StatusCode, WalletAssetsResponse) =
+        api.get_with_status(&format!("/v1/wallets/{}/assets?height=1", pub_key.to_string()));
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().total, 0);
+
+    let (status, response): (StatusCode, WalletAssetsResponse) =
+        api.get_with_status(&format!("/v1/wallets/{}/assets?height=2", pub_key.to_string()));
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(response.unwrap().assets, vec![ExtendedAsset::from_asset(&asset, None)]);
+}
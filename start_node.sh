@@ -3,4 +3,11 @@
 export RUST_LOG=info
 export CONFIG_PATH="./etc/config$1.toml"
 
-cargo run -p dmbc-node
\ No newline at end of file
+# Generate the keys on the first start; the passphrase is asked on the
+# terminal unless DMBC_KEYS_PASSPHRASE or DMBC_KEYS_PASSPHRASE_FILE is set.
+KEYS_PATH=$(cargo run -q -p dmbc-node -- print-config | sed -n 's/^keys_path = "\(.*\)"$/\1/p')
+if [ ! -e "${KEYS_PATH:-./var/keys}/consensus" ]; then
+    cargo run -p dmbc-node -- generate-keys || exit 1
+fi
+
+cargo run -p dmbc-node